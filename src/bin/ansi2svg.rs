@@ -0,0 +1,13 @@
+use std::io::{self, Read};
+
+use rustcolor::svg::SvgRenderer;
+
+fn main() {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .expect("failed to read the standard input");
+
+    let title = std::env::args().nth(1).unwrap_or_default();
+    print!("{}", SvgRenderer::new().title(&title).render(&input));
+}
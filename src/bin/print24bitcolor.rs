@@ -18,21 +18,21 @@ fn main() {
     for i in (0..=255).step_by(step) {
         print!(
             "{}",
-            " ".print_24bit(RGB(0, 0, 0), RGB(255 - i, 255 - 0, 255 - 0))
+            " ".print_24bit(RGB(0, 0, 0), RGB(255 - i, 255, 255))
         );
     }
     println!();
     for i in (0..=255).step_by(step) {
         print!(
             "{}",
-            " ".print_24bit(RGB(0, 0, 0), RGB(255 - 0, 255 - i, 255 - 0))
+            " ".print_24bit(RGB(0, 0, 0), RGB(255, 255 - i, 255))
         );
     }
     println!();
     for i in (0..=255).step_by(step) {
         print!(
             "{}",
-            " ".print_24bit(RGB(0, 0, 0), RGB(255 - 0, 255 - 0, 255 - i))
+            " ".print_24bit(RGB(0, 0, 0), RGB(255, 255, 255 - i))
         );
     }
     println!();
@@ -1,5 +1,3 @@
-use std::usize;
-
 use rustcolor::{color::*, printer::ColorPrinter};
 
 macro_rules! print_color_pallette {
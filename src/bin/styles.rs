@@ -1,11 +1,19 @@
-use rustcolor::{blink, danger, error, info, primary, printer::ColorPrinter, underline, warn};
+use rustcolor::{
+    blink, code, danger, emphasis, error, info, link, muted, primary, printer::ColorPrinter,
+    success, underline, warn,
+};
 
 fn main() {
     info!("this is the info style");
+    success!("this is the success style");
     primary!("this is the primary style");
     warn!("this is the warn style");
     danger!("this is the danger style");
     error!("this is the error style");
+    muted!("this is the muted style");
+    emphasis!("this is the emphasis style");
+    code!("this is the code style");
+    link!("this is the link style");
     underline!("this is the underlined style");
     blink!("this is the blink style");
 }
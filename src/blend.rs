@@ -0,0 +1,179 @@
+/*!
+# blend
+This module layers translucent colors over opaque backgrounds. An [`Rgba`] color is
+composited onto a background with the source over operator, optionally through a
+[`BlendMode`] as defined by the
+[W3C compositing specification](https://www.w3.org/TR/compositing-1/#blending), so the
+result is a concrete [`RGB`] a terminal can print.
+
+# Examples
+
+```
+use rustcolor::blend::{BlendMode, Rgba};
+use rustcolor::color::RGB;
+use rustcolor::printer::ColorPrinter;
+
+let background = RGB(30, 30, 30);
+let highlight: Rgba = "#ffcc0040".parse().unwrap();
+let selection = highlight.over(background);
+assert_eq!(RGB(86, 74, 22), selection);
+
+let shadow = Rgba::new(RGB(128, 128, 128), 1.0).blend(background, BlendMode::Multiply);
+assert_eq!(RGB(15, 15, 15), shadow);
+
+println!("{}", "selected text".print_24bit(RGB(255, 255, 255), selection));
+```
+*/
+
+use crate::color::{ParseColorError, RGB};
+use crate::space::{from_unit, unit};
+use std::fmt;
+use std::str::FromStr;
+
+/// A color with an alpha channel, 0 being transparent and 255 opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgba(pub u8, pub u8, pub u8, pub u8);
+
+/// How the colors of a layer mix with the colors below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// The layer color replaces the backdrop.
+    Normal,
+    /// Darkens by multiplying the colors.
+    Multiply,
+    /// Lightens by multiplying the inverted colors.
+    Screen,
+    /// Multiplies the dark parts of the backdrop and screens its light parts.
+    Overlay,
+    /// Darkens or lightens the backdrop softly, depending on the layer color.
+    SoftLight,
+    /// Subtracts the darker of the colors from the lighter one.
+    Difference,
+}
+
+impl Rgba {
+    /// Creates a color from an opaque color and an opacity in 0.0-1.0.
+    pub fn new(color: RGB, alpha: f64) -> Self {
+        let RGB(r, g, b) = color;
+        Rgba(r, g, b, (alpha.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Returns the color without its alpha channel.
+    pub fn rgb(self) -> RGB {
+        RGB(self.0, self.1, self.2)
+    }
+
+    /// Returns the opacity in 0.0-1.0.
+    pub fn alpha(self) -> f64 {
+        self.3 as f64 / 255.0
+    }
+
+    /// Composites the color onto an opaque background.
+    pub fn over(self, background: RGB) -> RGB {
+        self.blend(background, BlendMode::Normal)
+    }
+
+    /// Composites the color onto an opaque background through a blend mode.
+    pub fn blend(self, background: RGB, mode: BlendMode) -> RGB {
+        let alpha = self.alpha();
+        let (sr, sg, sb) = unit(self.rgb());
+        let (br, bg, bb) = unit(background);
+        let channel = |source: f64, backdrop: f64| {
+            backdrop + (mode.apply(backdrop, source) - backdrop) * alpha
+        };
+        from_unit(channel(sr, br), channel(sg, bg), channel(sb, bb))
+    }
+
+    /// Composites the color onto another translucent color.
+    pub fn over_rgba(self, background: Rgba) -> Rgba {
+        let (alpha, below) = (self.alpha(), background.alpha());
+        let combined = alpha + below * (1.0 - alpha);
+        if combined == 0.0 {
+            return Rgba(0, 0, 0, 0);
+        }
+        let (sr, sg, sb) = unit(self.rgb());
+        let (br, bg, bb) = unit(background.rgb());
+        let channel = |source: f64, backdrop: f64| {
+            (source * alpha + backdrop * below * (1.0 - alpha)) / combined
+        };
+        Rgba::new(
+            from_unit(channel(sr, br), channel(sg, bg), channel(sb, bb)),
+            combined,
+        )
+    }
+}
+
+impl BlendMode {
+    /// Blends a backdrop and a source component, both in 0.0-1.0.
+    pub fn apply(self, backdrop: f64, source: f64) -> f64 {
+        match self {
+            BlendMode::Normal => source,
+            BlendMode::Multiply => backdrop * source,
+            BlendMode::Screen => backdrop + source - backdrop * source,
+            BlendMode::Overlay => {
+                if backdrop <= 0.5 {
+                    BlendMode::Multiply.apply(source, 2.0 * backdrop)
+                } else {
+                    BlendMode::Screen.apply(source, 2.0 * backdrop - 1.0)
+                }
+            }
+            BlendMode::SoftLight => {
+                if source <= 0.5 {
+                    backdrop - (1.0 - 2.0 * source) * backdrop * (1.0 - backdrop)
+                } else {
+                    let d = if backdrop <= 0.25 {
+                        ((16.0 * backdrop - 12.0) * backdrop + 4.0) * backdrop
+                    } else {
+                        backdrop.sqrt()
+                    };
+                    backdrop + (2.0 * source - 1.0) * (d - backdrop)
+                }
+            }
+            BlendMode::Difference => (backdrop - source).abs(),
+        }
+    }
+}
+
+impl From<RGB> for Rgba {
+    fn from(RGB(r, g, b): RGB) -> Self {
+        Rgba(r, g, b, 255)
+    }
+}
+
+impl FromStr for Rgba {
+    type Err = ParseColorError;
+
+    /// Parses `#rgba` or `#rrggbbaa`, or any opaque notation [`RGB`] parses.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let trimmed = text.trim();
+        let digits = match trimmed.strip_prefix('#') {
+            Some(digits) if digits.len() == 4 || digits.len() == 8 => digits,
+            _ => return RGB::from_str(text).map(Rgba::from),
+        };
+        let invalid = || ParseColorError::InvalidHex(text.to_owned());
+        if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let len = digits.len() / 4;
+        let component = |index: usize| {
+            let value = u8::from_str_radix(&digits[index * len..(index + 1) * len], 16).unwrap();
+            if len == 1 {
+                value * 17
+            } else {
+                value
+            }
+        };
+        Ok(Rgba(component(0), component(1), component(2), component(3)))
+    }
+}
+
+impl fmt::Display for Rgba {
+    /// Formats the color as `#rrggbbaa`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{:02x}{:02x}{:02x}{:02x}",
+            self.0, self.1, self.2, self.3
+        )
+    }
+}
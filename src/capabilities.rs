@@ -0,0 +1,190 @@
+/*!
+# capabilities
+This module parses the SGR capability strings many tools read their colors from, as
+`GREP_COLORS` (`ms=01;31:mc=01;31:sl=:cx=:fn=35`) or `GCC_COLORS`
+(`error=01;31:warning=01;35`). Entries are separated by colons, each one is a
+capability name with a SGR parameter list, or a boolean capability without value, as
+the `ne` and `rv` flags of grep. Later entries override earlier ones, so user settings
+are merged over the defaults of a tool.
+
+Git style color specifications, as `bold red ul`, are parsed by [`TextStyle::from_git`].
+
+# Examples
+
+```
+use rustcolor::capabilities::Capabilities;
+use rustcolor::style::TextStyle;
+
+let grep = Capabilities::grep_defaults()
+    .merge(Capabilities::parse("mt=01;32:ne").unwrap());
+assert_eq!(Some(TextStyle::from_sgr("1;32").unwrap()), grep.style("ms"));
+assert_eq!(Some(TextStyle::from_sgr("35").unwrap()), grep.style("fn"));
+assert_eq!(Some(TextStyle::default()), grep.style("sl"));
+assert!(grep.flag("ne"));
+
+println!("{}", grep.paint("fn", "src/lib.rs"));
+```
+*/
+
+use crate::style::{SgrError, TextStyle};
+use std::env;
+use std::error::Error;
+use std::fmt;
+
+/// The default capabilities of GNU grep.
+pub const GREP_DEFAULTS: &str = "ms=01;31:mc=01;31:sl=:cx=:fn=35:ln=32:bn=32:se=36";
+/// The default capabilities of GCC.
+pub const GCC_DEFAULTS: &str = "error=01;31:warning=01;35:note=01;36:range1=32:range2=34:\
+                                locus=01:quote=01:path=01;36:fixit-insert=32:fixit-delete=31:\
+                                diff-filename=01:diff-hunk=32:diff-delete=31:diff-insert=32:\
+                                type-diff=01;32";
+
+/// Error returned when a capability string can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapabilityError {
+    /// An entry without a capability name.
+    MissingName(String),
+    /// A value that is not a SGR parameter list.
+    InvalidStyle { name: String, error: SgrError },
+}
+
+impl fmt::Display for CapabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CapabilityError::MissingName(entry) => {
+                write!(f, "missing capability name in '{}'", entry)
+            }
+            CapabilityError::InvalidStyle { name, error } => {
+                write!(f, "invalid style for '{}': {}", name, error)
+            }
+        }
+    }
+}
+
+impl Error for CapabilityError {}
+
+/// The capabilities of a capability string, in order, with the style of each one or
+/// `None` for the boolean capabilities.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    entries: Vec<(String, Option<TextStyle>)>,
+}
+
+impl Capabilities {
+    /// Creates an empty set of capabilities.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a capability string, as `ms=01;31:sl=:ne`.
+    pub fn parse(text: &str) -> Result<Self, CapabilityError> {
+        let mut capabilities = Capabilities::new();
+        for entry in text.split(':').filter(|entry| !entry.is_empty()) {
+            let (name, value) = match entry.split_once('=') {
+                Some((name, sgr)) => {
+                    let style = TextStyle::from_sgr(sgr).map_err(|error| {
+                        CapabilityError::InvalidStyle {
+                            name: name.to_owned(),
+                            error,
+                        }
+                    })?;
+                    (name, Some(style))
+                }
+                None => (entry, None),
+            };
+            if name.is_empty() {
+                return Err(CapabilityError::MissingName(entry.to_owned()));
+            }
+            capabilities.entries.push((name.to_owned(), value));
+        }
+        Ok(capabilities)
+    }
+
+    /// Parses the defaults, then the value of an environment variable over them.
+    /// An unset or invalid variable leaves the defaults.
+    pub fn from_env(variable: &str, defaults: &str) -> Self {
+        let defaults = Capabilities::parse(defaults).unwrap_or_default();
+        match env::var(variable).map(|value| Capabilities::parse(&value)) {
+            Ok(Ok(capabilities)) => defaults.merge(capabilities),
+            _ => defaults,
+        }
+    }
+
+    /// The default capabilities of GNU grep.
+    pub fn grep_defaults() -> Self {
+        Capabilities::parse(GREP_DEFAULTS).expect("the grep defaults are valid")
+    }
+
+    /// The capabilities of GNU grep, `GREP_COLORS` over the defaults. `mt` sets both
+    /// `ms` and `mc`.
+    pub fn grep() -> Self {
+        Capabilities::from_env("GREP_COLORS", GREP_DEFAULTS)
+    }
+
+    /// The default capabilities of GCC.
+    pub fn gcc_defaults() -> Self {
+        Capabilities::parse(GCC_DEFAULTS).expect("the GCC defaults are valid")
+    }
+
+    /// The capabilities of GCC, `GCC_COLORS` over the defaults. An empty `GCC_COLORS`
+    /// disables all colors.
+    pub fn gcc() -> Self {
+        match env::var("GCC_COLORS") {
+            Ok(value) if value.is_empty() => Capabilities::new(),
+            _ => Capabilities::from_env("GCC_COLORS", GCC_DEFAULTS),
+        }
+    }
+
+    /// Returns the capabilities with the entries of another set added after theirs,
+    /// taking precedence.
+    pub fn merge(mut self, other: Capabilities) -> Capabilities {
+        self.entries.extend(other.entries);
+        self
+    }
+
+    /// Sets the style of a capability.
+    pub fn set(mut self, name: &str, style: TextStyle) -> Capabilities {
+        self.entries.push((name.to_owned(), Some(style)));
+        self
+    }
+
+    /// Returns the style of a capability, from its last entry. The grep `mt`
+    /// capability stands for `ms` and `mc`.
+    pub fn style(&self, name: &str) -> Option<TextStyle> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(entry, _)| entry == name || (entry == "mt" && (name == "ms" || name == "mc")))
+            .and_then(|(_, style)| *style)
+    }
+
+    /// Tells whether a boolean capability is set.
+    pub fn flag(&self, name: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|(entry, style)| entry == name && style.is_none())
+    }
+
+    /// Returns the text styled with a capability, or unchanged if it has no style.
+    pub fn paint(&self, name: &str, text: &str) -> String {
+        match self.style(name) {
+            Some(style) => style.render(text),
+            None => text.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Capabilities {
+    /// Formats the capabilities as a capability string, as [`Capabilities::parse`] parses it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(name, style)| match style {
+                Some(style) => format!("{}={}", name, style.to_sgr()),
+                None => name.clone(),
+            })
+            .collect();
+        write!(f, "{}", entries.join(":"))
+    }
+}
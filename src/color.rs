@@ -22,16 +22,33 @@ As [256-color](https://en.wikipedia.org/wiki/8-bit_color) lookup tables became c
 
 * ESC[ 38;5;⟨n⟩m Select foreground color
 * ESC[ 48;5;⟨n⟩m Select background color \
-* ESC[ 38;5;⟨n1⟩;48;5;⟨n2⟩m both foreground and background \
+* ESC[ 38;5;⟨n1⟩;48;5;⟨n2⟩m both foreground and background
+
 0 - 7:  standard colors (as in ESC [ 30–37 m) \
 8 - 15:  high intensity colors (as in ESC [ 90–97 m) \
 16 - 231:  6 × 6 × 6 cube (216 colors): 16 + 36 × r + 6 × g + b (0 ≤ r, g, b ≤ 5) \
 232-255:  grayscale from black to white in 24 steps
 
 ### 24bit
+
+24bit colors are written as **ESC[38;2;⟨r⟩;⟨g⟩;⟨b⟩m** and **ESC[48;2;⟨r⟩;⟨g⟩;⟨b⟩m**. The [`RGB`]
+type holds them, and parses the usual notations of config files.
+
+```
+use rustcolor::color::RGB;
+
+let color: RGB = "#ff8800".parse().unwrap();
+assert_eq!(RGB(255, 136, 0), color);
+assert_eq!(color, "#f80".parse().unwrap());
+assert_eq!(color, "rgb(255, 136, 0)".parse().unwrap());
+assert_eq!(color, "0xFF8800".parse().unwrap());
+assert_eq!("#ff8800", color.to_string());
+```
 */
 
-use std::usize;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
 
 pub const FG_BLACK: usize = 30;
 pub const FG_RED: usize = 31;
@@ -71,12 +88,12 @@ pub const BG_LIGHT_MAGENTA: usize = 105;
 pub const BG_LIGHT_CYAN: usize = 106;
 pub const BG_LIGHT_WHITE: usize = 107;
 
-/// darken the color if it has 4bit
+/// darken the color if it has 4bit, see [`RGB::darken`] for 24bit colors
 pub fn darken(color: usize) -> usize {
     color - 60
 }
 
-/// lighten the color if it has 4bit
+/// lighten the color if it has 4bit, see [`RGB::lighten`] for 24bit colors
 pub fn lighten(color: usize) -> usize {
     color + 60
 }
@@ -364,4 +381,264 @@ pub const C8_254: usize = 254;
 pub const C8_255: usize = 255;
 
 /// RGB color data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct RGB(pub u8, pub u8, pub u8);
+
+impl RGB {
+    /// Creates a color from its red, green and blue components.
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        RGB(red, green, blue)
+    }
+
+    /// Creates a color from a `0xRRGGBB` integer, ignoring the upper byte.
+    pub const fn from_u32(value: u32) -> Self {
+        RGB((value >> 16) as u8, (value >> 8) as u8, value as u8)
+    }
+
+    /// Returns the color as a `0xRRGGBB` integer.
+    pub const fn to_u32(self) -> u32 {
+        (self.0 as u32) << 16 | (self.1 as u32) << 8 | self.2 as u32
+    }
+
+    /// Parses a hexadecimal color: `#rgb`, `#rrggbb` or `#rrggbbaa`, where the alpha is
+    /// ignored, or `0xRRGGBB`.
+    pub fn from_hex(hex: &str) -> Result<RGB, ParseColorError> {
+        let text = hex.trim();
+        let digits = text
+            .strip_prefix('#')
+            .or_else(|| text.strip_prefix("0x"))
+            .or_else(|| text.strip_prefix("0X"))
+            .ok_or_else(|| ParseColorError::UnknownFormat(hex.to_owned()))?;
+        let invalid = || ParseColorError::InvalidHex(hex.to_owned());
+        if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let component = |index: usize, len: usize| {
+            let value = u8::from_str_radix(&digits[index * len..(index + 1) * len], 16).unwrap();
+            if len == 1 {
+                value * 17
+            } else {
+                value
+            }
+        };
+        match (text.starts_with('#'), digits.len()) {
+            (true, 3) => Ok(RGB(component(0, 1), component(1, 1), component(2, 1))),
+            (_, 6) | (true, 8) => Ok(RGB(component(0, 2), component(1, 2), component(2, 2))),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl FromStr for RGB {
+    type Err = ParseColorError;
+
+    /// Parses `#rgb`, `#rrggbb`, `#rrggbbaa`, `0xRRGGBB`, `rgb(r, g, b)` or the X11
+    /// `rgb:r/g/b` notation, with 1 to 4 hexadecimal digits per component.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let trimmed = text.trim();
+        let lowercase = trimmed.to_ascii_lowercase();
+        if let Some(spec) = lowercase
+            .strip_prefix("rgb:")
+            .or_else(|| lowercase.strip_prefix("rgba:"))
+        {
+            return from_x11_spec(spec, lowercase.starts_with("rgba:"))
+                .ok_or_else(|| ParseColorError::InvalidHex(text.to_owned()));
+        }
+        if let Some(arguments) = lowercase
+            .strip_prefix("rgb(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let components: Vec<&str> = arguments.split(',').map(str::trim).collect();
+            if components.len() != 3 {
+                return Err(ParseColorError::UnknownFormat(text.to_owned()));
+            }
+            let mut values = [0; 3];
+            for (value, component) in values.iter_mut().zip(components) {
+                *value = component
+                    .parse::<u8>()
+                    .map_err(|_| ParseColorError::InvalidComponent(component.to_owned()))?;
+            }
+            return Ok(RGB(values[0], values[1], values[2]));
+        }
+        RGB::from_hex(trimmed).map_err(|error| match error {
+            ParseColorError::UnknownFormat(_) => ParseColorError::UnknownFormat(text.to_owned()),
+            error => error,
+        })
+    }
+}
+
+/// Parses the `r/g/b` components of an X11 color specification, scaling each one from
+/// its number of hexadecimal digits to 8 bits. The alpha of `rgba:` is ignored.
+fn from_x11_spec(spec: &str, alpha: bool) -> Option<RGB> {
+    let components: Vec<&str> = spec.split('/').collect();
+    if components.len() != if alpha { 4 } else { 3 } {
+        return None;
+    }
+    let mut values = [0; 4];
+    for (value, component) in values.iter_mut().zip(components) {
+        if component.is_empty()
+            || component.len() > 4
+            || !component.bytes().all(|byte| byte.is_ascii_hexdigit())
+        {
+            return None;
+        }
+        let max = (1u32 << (4 * component.len())) - 1;
+        let parsed = u32::from_str_radix(component, 16).ok()?;
+        *value = ((parsed * 255 + max / 2) / max) as u8;
+    }
+    Some(RGB(values[0], values[1], values[2]))
+}
+
+impl fmt::Display for RGB {
+    /// Formats the color as `#rrggbb`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl From<(u8, u8, u8)> for RGB {
+    fn from((red, green, blue): (u8, u8, u8)) -> Self {
+        RGB(red, green, blue)
+    }
+}
+
+/// Error returned when a color can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The text is not written in any supported color notation.
+    UnknownFormat(String),
+    /// A hexadecimal color with invalid digits or an invalid number of digits.
+    InvalidHex(String),
+    /// A `rgb()` component that is not an integer between 0 and 255.
+    InvalidComponent(String),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseColorError::UnknownFormat(text) => write!(f, "unknown color format '{}'", text),
+            ParseColorError::InvalidHex(text) => write!(f, "invalid hexadecimal color '{}'", text),
+            ParseColorError::InvalidComponent(text) => {
+                write!(f, "invalid color component '{}', expected 0-255", text)
+            }
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// A terminal color, as selected by the SGR color parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The terminal default color (SGR 39 and 49).
+    Default,
+    /// One of the 16 system colors, 0-7 standard and 8-15 high intensity.
+    C16(u8),
+    /// One of the 256 colors of the 8bit lookup table.
+    C256(u8),
+    /// A 24bit color.
+    Rgb(RGB),
+}
+
+impl Color {
+    /// Converts a 3/4 bit foreground code (30-37, 39 and 90-97) into a color.
+    pub fn from_foreground_code(code: usize) -> Option<Color> {
+        Color::from_code(code, FG_BLACK)
+    }
+
+    /// Converts a 3/4 bit background code (40-47, 49 and 100-107) into a color.
+    pub fn from_background_code(code: usize) -> Option<Color> {
+        Color::from_code(code, BG_BLACK)
+    }
+
+    /// Returns the SGR parameters that select this color as foreground.
+    pub fn foreground_sgr(&self) -> String {
+        self.sgr(FG_BLACK)
+    }
+
+    /// Returns the SGR parameters that select this color as background.
+    pub fn background_sgr(&self) -> String {
+        self.sgr(BG_BLACK)
+    }
+
+    fn from_code(code: usize, base: usize) -> Option<Color> {
+        match code {
+            c if (base..base + 8).contains(&c) => Some(Color::C16((c - base) as u8)),
+            c if c == base + 9 => Some(Color::Default),
+            c if (base + 60..base + 68).contains(&c) => Some(Color::C16((c - base - 52) as u8)),
+            _ => None,
+        }
+    }
+
+    fn sgr(&self, base: usize) -> String {
+        match *self {
+            Color::Default => (base + 9).to_string(),
+            Color::C16(n) if n & 8 == 0 => (base + (n & 7) as usize).to_string(),
+            Color::C16(n) => lighten(base + (n & 7) as usize).to_string(),
+            Color::C256(n) => format!("{};5;{}", base + 8, n),
+            Color::Rgb(RGB(r, g, b)) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+}
+
+/// The names of the 8 standard colors, prefixed by `bright-` for the high intensity ones.
+pub(crate) const SYSTEM_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses `default`, a system color name such as `red` or `bright-red`, a 256 color
+    /// index, or a 24bit color in any [`RGB`] notation or CSS and X11 name. Dashes,
+    /// underscores and spaces are ignored in names only, an index being plain digits.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let name: String = text
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if name == "default" {
+            return Ok(Color::Default);
+        }
+        let (bright, base) = match name.strip_prefix("bright") {
+            Some(base) => (8, base),
+            None => (0, name.as_str()),
+        };
+        if let Some(index) = SYSTEM_NAMES.iter().position(|system| *system == base) {
+            return Ok(Color::C16(index as u8 + bright));
+        }
+        let trimmed = text.trim();
+        if trimmed.bytes().any(|byte| byte.is_ascii_digit())
+            && trimmed
+                .bytes()
+                .all(|byte| byte.is_ascii_digit() || b"+-_ ".contains(&byte))
+        {
+            let invalid = || ParseColorError::InvalidComponent(trimmed.to_owned());
+            if !trimmed.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            return trimmed.parse().map(Color::C256).map_err(|_| invalid());
+        }
+        match text.parse::<RGB>() {
+            Err(ParseColorError::UnknownFormat(_)) => crate::names::lookup(text.trim())
+                .map(Color::Rgb)
+                .ok_or_else(|| ParseColorError::UnknownFormat(text.to_owned())),
+            result => result.map(Color::Rgb),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    /// Formats the color in the notation [`Color::from_str`] parses.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Color::Default => write!(f, "default"),
+            Color::C16(n) if n & 8 == 0 => write!(f, "{}", SYSTEM_NAMES[(n & 7) as usize]),
+            Color::C16(n) => write!(f, "bright-{}", SYSTEM_NAMES[(n & 7) as usize]),
+            Color::C256(n) => write!(f, "{}", n),
+            Color::Rgb(rgb) => write!(f, "{}", rgb),
+        }
+    }
+}
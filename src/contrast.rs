@@ -0,0 +1,148 @@
+/*!
+# contrast
+This module checks that text stays readable on its background. It computes the
+[WCAG 2.x](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) contrast ratio, from 1 to 21,
+and the [APCA](https://github.com/Myndex/apca-w3) lightness contrast, about -108 to 106,
+positive for dark text on a light background. 16 and 256 colors are resolved through
+a [`Palette`].
+
+# Examples
+
+```
+use rustcolor::color::{Color, RGB};
+use rustcolor::contrast::{self, AA_NORMAL};
+use rustcolor::palette::Palette;
+
+let white = RGB(255, 255, 255);
+assert_eq!(21.0, contrast::contrast_ratio(RGB(0, 0, 0), white));
+
+// yellow on white is unreadable, a darker yellow is found instead
+let palette = Palette::default();
+let yellow = palette.resolve(Color::C16(11)).unwrap();
+assert!(contrast::contrast_ratio(yellow, white) < AA_NORMAL);
+let fixed = contrast::adjust_foreground(yellow, white, AA_NORMAL);
+assert!(contrast::contrast_ratio(fixed, white) >= AA_NORMAL);
+```
+*/
+
+use crate::color::RGB;
+use crate::palette::Palette;
+use crate::space::{LinearRgb, Oklch};
+use crate::style::TextStyle;
+
+/// WCAG level AA minimum ratio for normal text.
+pub const AA_NORMAL: f64 = 4.5;
+/// WCAG level AA minimum ratio for large text.
+pub const AA_LARGE: f64 = 3.0;
+/// WCAG level AAA minimum ratio for normal text.
+pub const AAA_NORMAL: f64 = 7.0;
+/// WCAG level AAA minimum ratio for large text.
+pub const AAA_LARGE: f64 = 4.5;
+
+/// The WCAG relative luminance of a color, in 0.0-1.0.
+pub fn relative_luminance(color: RGB) -> f64 {
+    let LinearRgb { r, g, b } = LinearRgb::from(color);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// The WCAG contrast ratio between two colors, in 1.0-21.0, whatever their order.
+pub fn contrast_ratio(a: RGB, b: RGB) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// The APCA lightness contrast of a text color on a background color.
+pub fn apca_contrast(text: RGB, background: RGB) -> f64 {
+    let text = apca_luminance(text);
+    let background = apca_luminance(background);
+    if (background - text).abs() < 0.0005 {
+        return 0.0;
+    }
+    let contrast = if background > text {
+        let sapc = (background.powf(0.56) - text.powf(0.57)) * 1.14;
+        if sapc < 0.1 {
+            0.0
+        } else {
+            sapc - 0.027
+        }
+    } else {
+        let sapc = (background.powf(0.65) - text.powf(0.62)) * 1.14;
+        if sapc > -0.1 {
+            0.0
+        } else {
+            sapc + 0.027
+        }
+    };
+    contrast * 100.0
+}
+
+/// The WCAG contrast ratio of a style, its colors resolved through a palette.
+pub fn style_contrast_ratio(style: &TextStyle, palette: &Palette) -> f64 {
+    let (foreground, background) = palette.resolve_style(style);
+    contrast_ratio(foreground, background)
+}
+
+/// The APCA lightness contrast of a style, its colors resolved through a palette.
+pub fn style_apca_contrast(style: &TextStyle, palette: &Palette) -> f64 {
+    let (foreground, background) = palette.resolve_style(style);
+    apca_contrast(foreground, background)
+}
+
+/// Picks the first candidate meeting the target contrast ratio against the background,
+/// or the candidate with the highest ratio if none does.
+pub fn pick_foreground(candidates: &[RGB], background: RGB, target: f64) -> Option<RGB> {
+    candidates
+        .iter()
+        .find(|candidate| contrast_ratio(**candidate, background) >= target)
+        .or_else(|| {
+            candidates.iter().max_by(|a, b| {
+                contrast_ratio(**a, background).total_cmp(&contrast_ratio(**b, background))
+            })
+        })
+        .copied()
+}
+
+/// Adjusts the OKLCh lightness of a foreground as little as possible, keeping its hue,
+/// until it meets the target contrast ratio against the background. If the target can
+/// not be met, returns black or white, whichever contrasts the most.
+pub fn adjust_foreground(foreground: RGB, background: RGB, target: f64) -> RGB {
+    if contrast_ratio(foreground, background) >= target {
+        return foreground;
+    }
+    let color = Oklch::from(foreground);
+    let lighter = relative_luminance(background) < 0.18;
+    let directions = if lighter { [1.0, 0.0] } else { [0.0, 1.0] };
+    for limit in directions.iter() {
+        let with_lightness = |l: f64| RGB::from(Oklch { l, ..color });
+        if contrast_ratio(with_lightness(*limit), background) < target {
+            continue;
+        }
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..32 {
+            let middle = (low + high) / 2.0;
+            let l = color.l + (limit - color.l) * middle;
+            if contrast_ratio(with_lightness(l), background) >= target {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        return with_lightness(color.l + (limit - color.l) * high);
+    }
+    pick_foreground(
+        &[RGB(0, 0, 0), RGB(255, 255, 255)],
+        background,
+        f64::INFINITY,
+    )
+    .unwrap()
+}
+
+fn apca_luminance(RGB(r, g, b): RGB) -> f64 {
+    let channel = |c: u8| (c as f64 / 255.0).powf(2.4);
+    let y = 0.212_672_9 * channel(r) + 0.715_152_2 * channel(g) + 0.072_175_0 * channel(b);
+    if y < 0.022 {
+        y + (0.022 - y).powf(1.414)
+    } else {
+        y
+    }
+}
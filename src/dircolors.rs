@@ -0,0 +1,809 @@
+/*!
+# dircolors
+This module colors file names the way GNU `ls` does. The styles are read from the
+`LS_COLORS` environment variable, as in `di=01;34:*.tar=01;31`, or from a `dircolors`
+database, as in `DIR 01;34` and `.tar 01;31`, and the GNU default database is built
+in. A file is styled by its type and permissions, and regular files by the suffix of
+their name, the later suffixes taking precedence. Suffixes are matched ignoring case,
+unless several of them differ only by case.
+
+As with `dircolors`, the `TERM` and `COLORTERM` lines of a database hold glob patterns,
+and the entries following a run of them only apply when one of the patterns matches
+the terminal. The entries before any such line always apply.
+
+# Examples
+
+```
+use rustcolor::dircolors::{Indicator, LsColors};
+use rustcolor::style::TextStyle;
+
+let colors = LsColors::from_ls_colors("di=01;34:ex=01;32:*.tar=01;31").unwrap();
+assert_eq!(
+    Some(TextStyle::from_sgr("1;34").unwrap()),
+    colors.style(Indicator::Directory)
+);
+assert_eq!(
+    Some(TextStyle::from_sgr("1;31").unwrap()),
+    colors.style_for_name("backup.TAR")
+);
+
+let gnu = LsColors::gnu();
+println!("{}", gnu.paint_name("song.mp3"));
+```
+*/
+
+use crate::style::{SgrError, TextStyle};
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, Metadata};
+use std::path::Path;
+
+/// The database of `dircolors --print-database`, from GNU coreutils, without its `TERM`
+/// and `COLORTERM` lines so it applies to every terminal.
+pub const GNU_DATABASE: &str = "\
+# Configuration file for dircolors, a utility to help you set the
+# LS_COLORS environment variable used by GNU ls with the --color option.
+#NORMAL 00 # no color code at all
+#FILE 00 # regular file: use no color at all
+RESET 0 # reset to \"normal\" color
+DIR 01;34 # directory
+LINK 01;36 # symbolic link. (If you set this to 'target' instead of a
+ # numerical value, the color is as for the file pointed to.)
+MULTIHARDLINK 00 # regular file with more than one link
+FIFO 40;33 # pipe
+SOCK 01;35 # socket
+DOOR 01;35 # door
+BLK 40;33;01 # block device driver
+CHR 40;33;01 # character device driver
+ORPHAN 40;31;01 # symlink to nonexistent file, or non-stat'able file ...
+MISSING 00 # ... and the files they point to
+SETUID 37;41 # file that is setuid (u+s)
+SETGID 30;43 # file that is setgid (g+s)
+CAPABILITY 00 # file with capability (very expensive to lookup)
+STICKY_OTHER_WRITABLE 30;42 # dir that is sticky and other-writable (+t,o+w)
+OTHER_WRITABLE 34;42 # dir that is other-writable (o+w) and not sticky
+STICKY 37;44 # dir with the sticky bit set (+t) and not other-writable
+# This is for files with execute permission:
+EXEC 01;32
+# archives or compressed (bright red)
+.7z 01;31
+.ace 01;31
+.alz 01;31
+.apk 01;31
+.arc 01;31
+.arj 01;31
+.bz 01;31
+.bz2 01;31
+.cab 01;31
+.cpio 01;31
+.crate 01;31
+.deb 01;31
+.drpm 01;31
+.dwm 01;31
+.dz 01;31
+.ear 01;31
+.egg 01;31
+.esd 01;31
+.gz 01;31
+.jar 01;31
+.lha 01;31
+.lrz 01;31
+.lz 01;31
+.lz4 01;31
+.lzh 01;31
+.lzma 01;31
+.lzo 01;31
+.pyz 01;31
+.rar 01;31
+.rpm 01;31
+.rz 01;31
+.sar 01;31
+.swm 01;31
+.t7z 01;31
+.tar 01;31
+.taz 01;31
+.tbz 01;31
+.tbz2 01;31
+.tgz 01;31
+.tlz 01;31
+.txz 01;31
+.tz 01;31
+.tzo 01;31
+.tzst 01;31
+.udeb 01;31
+.war 01;31
+.whl 01;31
+.wim 01;31
+.xz 01;31
+.z 01;31
+.zip 01;31
+.zoo 01;31
+.zst 01;31
+# image formats
+.avif 01;35
+.jpg 01;35
+.jpeg 01;35
+.jxl 01;35
+.mjpg 01;35
+.mjpeg 01;35
+.gif 01;35
+.bmp 01;35
+.pbm 01;35
+.pgm 01;35
+.ppm 01;35
+.tga 01;35
+.xbm 01;35
+.xpm 01;35
+.tif 01;35
+.tiff 01;35
+.png 01;35
+.svg 01;35
+.svgz 01;35
+.mng 01;35
+.pcx 01;35
+.mov 01;35
+.mpg 01;35
+.mpeg 01;35
+.m2v 01;35
+.mkv 01;35
+.webm 01;35
+.webp 01;35
+.ogm 01;35
+.mp4 01;35
+.m4v 01;35
+.mp4v 01;35
+.vob 01;35
+.qt 01;35
+.nuv 01;35
+.wmv 01;35
+.asf 01;35
+.rm 01;35
+.rmvb 01;35
+.flc 01;35
+.avi 01;35
+.fli 01;35
+.flv 01;35
+.gl 01;35
+.dl 01;35
+.xcf 01;35
+.xwd 01;35
+.yuv 01;35
+.cgm 01;35
+.emf 01;35
+# https://wiki.xiph.org/MIME_Types_and_File_Extensions
+.ogv 01;35
+.ogx 01;35
+# audio formats
+.aac 00;36
+.au 00;36
+.flac 00;36
+.m4a 00;36
+.mid 00;36
+.midi 00;36
+.mka 00;36
+.mp3 00;36
+.mpc 00;36
+.ogg 00;36
+.ra 00;36
+.wav 00;36
+# https://wiki.xiph.org/MIME_Types_and_File_Extensions
+.oga 00;36
+.opus 00;36
+.spx 00;36
+.xspf 00;36
+# backup files
+*~ 00;90
+*# 00;90
+.bak 00;90
+.crdownload 00;90
+.dpkg-dist 00;90
+.dpkg-new 00;90
+.dpkg-old 00;90
+.dpkg-tmp 00;90
+.old 00;90
+.orig 00;90
+.part 00;90
+.rej 00;90
+.rpmnew 00;90
+.rpmorig 00;90
+.rpmsave 00;90
+.swp 00;90
+.tmp 00;90
+.ucf-dist 00;90
+.ucf-new 00;90
+.ucf-old 00;90
+";
+
+/// The kinds of files `ls` colors, with their `LS_COLORS` code and database keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indicator {
+    /// Any text which is not a file name.
+    Normal,
+    /// A regular file.
+    File,
+    Directory,
+    Symlink,
+    /// A regular file with more than one hard link.
+    MultiHardLink,
+    Fifo,
+    Socket,
+    Door,
+    BlockDevice,
+    CharDevice,
+    /// A symbolic link to a missing file.
+    Orphan,
+    /// The missing target of an orphan symbolic link.
+    Missing,
+    Setuid,
+    Setgid,
+    Capability,
+    /// A sticky directory writable by others.
+    StickyOtherWritable,
+    /// A directory writable by others, not sticky.
+    OtherWritable,
+    /// A sticky directory, not writable by others.
+    Sticky,
+    Executable,
+}
+
+impl Indicator {
+    /// All the indicators, in the order of the `dircolors` database.
+    pub const ALL: [Indicator; 19] = [
+        Indicator::Normal,
+        Indicator::File,
+        Indicator::Directory,
+        Indicator::Symlink,
+        Indicator::MultiHardLink,
+        Indicator::Fifo,
+        Indicator::Socket,
+        Indicator::Door,
+        Indicator::BlockDevice,
+        Indicator::CharDevice,
+        Indicator::Orphan,
+        Indicator::Missing,
+        Indicator::Setuid,
+        Indicator::Setgid,
+        Indicator::Capability,
+        Indicator::StickyOtherWritable,
+        Indicator::OtherWritable,
+        Indicator::Sticky,
+        Indicator::Executable,
+    ];
+
+    /// Returns the two letter code of the indicator in `LS_COLORS`.
+    pub fn code(self) -> &'static str {
+        self.names().0
+    }
+
+    /// Returns the keyword of the indicator in a `dircolors` database.
+    pub fn keyword(self) -> &'static str {
+        self.names().1
+    }
+
+    /// Returns the indicator of a `LS_COLORS` code.
+    pub fn from_code(code: &str) -> Option<Indicator> {
+        Indicator::ALL
+            .iter()
+            .copied()
+            .find(|indicator| indicator.code() == code)
+    }
+
+    /// Returns the indicator of a `dircolors` keyword, ignoring case, with the
+    /// aliases `dircolors` accepts.
+    pub fn from_keyword(keyword: &str) -> Option<Indicator> {
+        let keyword = keyword.to_ascii_uppercase();
+        let keyword = match keyword.as_str() {
+            "SYMLINK" => "LINK",
+            "PIPE" => "FIFO",
+            "BLOCK" => "BLK",
+            "CHAR" => "CHR",
+            "HARDLINK" => "MULTIHARDLINK",
+            keyword => keyword,
+        };
+        Indicator::ALL
+            .iter()
+            .copied()
+            .find(|indicator| indicator.keyword() == keyword)
+    }
+
+    fn names(self) -> (&'static str, &'static str) {
+        match self {
+            Indicator::Normal => ("no", "NORMAL"),
+            Indicator::File => ("fi", "FILE"),
+            Indicator::Directory => ("di", "DIR"),
+            Indicator::Symlink => ("ln", "LINK"),
+            Indicator::MultiHardLink => ("mh", "MULTIHARDLINK"),
+            Indicator::Fifo => ("pi", "FIFO"),
+            Indicator::Socket => ("so", "SOCK"),
+            Indicator::Door => ("do", "DOOR"),
+            Indicator::BlockDevice => ("bd", "BLK"),
+            Indicator::CharDevice => ("cd", "CHR"),
+            Indicator::Orphan => ("or", "ORPHAN"),
+            Indicator::Missing => ("mi", "MISSING"),
+            Indicator::Setuid => ("su", "SETUID"),
+            Indicator::Setgid => ("sg", "SETGID"),
+            Indicator::Capability => ("ca", "CAPABILITY"),
+            Indicator::StickyOtherWritable => ("tw", "STICKY_OTHER_WRITABLE"),
+            Indicator::OtherWritable => ("ow", "OTHER_WRITABLE"),
+            Indicator::Sticky => ("st", "STICKY"),
+            Indicator::Executable => ("ex", "EXEC"),
+        }
+    }
+}
+
+/// The `LS_COLORS` codes of the escape sequences `ls` writes around names, which
+/// this module leaves to [`TextStyle::render`].
+const ESCAPE_CODES: [&str; 5] = ["lc", "rc", "ec", "rs", "cl"];
+/// The database keywords of the escape sequences, and the options `ls` ignores.
+const ESCAPE_KEYWORDS: [&str; 9] = [
+    "LEFTCODE",
+    "LEFT",
+    "RIGHTCODE",
+    "RIGHT",
+    "ENDCODE",
+    "END",
+    "RESET",
+    "CLRTOEOL",
+    "OPTIONS",
+];
+
+/// Error returned when `LS_COLORS` or a `dircolors` database can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DircolorsError {
+    /// A `LS_COLORS` code or a database keyword that is not known.
+    UnknownKey(String),
+    /// A value that is not a SGR parameter list.
+    InvalidStyle { key: String, error: SgrError },
+    /// A malformed entry, at the given line of a database or entry of `LS_COLORS`.
+    Syntax { line: usize, text: String },
+}
+
+impl fmt::Display for DircolorsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DircolorsError::UnknownKey(key) => write!(f, "unknown file type '{}'", key),
+            DircolorsError::InvalidStyle { key, error } => {
+                write!(f, "invalid style for '{}': {}", key, error)
+            }
+            DircolorsError::Syntax { line, text } => {
+                write!(f, "malformed entry {}: '{}'", line, text)
+            }
+        }
+    }
+}
+
+impl Error for DircolorsError {}
+
+/// A suffix of file names and its style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Suffix {
+    suffix: String,
+    style: TextStyle,
+    /// Set when another suffix differs only by case.
+    exact: bool,
+}
+
+/// The styles of file types and file name suffixes, as used by GNU `ls`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LsColors {
+    indicators: HashMap<Indicator, TextStyle>,
+    suffixes: Vec<Suffix>,
+    link_target: bool,
+}
+
+impl LsColors {
+    /// Creates an empty set of styles, coloring nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The styles of the GNU default database.
+    pub fn gnu() -> Self {
+        LsColors::from_dircolors(GNU_DATABASE).expect("the GNU database is valid")
+    }
+
+    /// Reads the `LS_COLORS` environment variable, or the GNU default database if it
+    /// is unset or invalid.
+    pub fn from_env() -> Self {
+        env::var("LS_COLORS")
+            .ok()
+            .and_then(|value| LsColors::from_ls_colors(&value).ok())
+            .unwrap_or_else(LsColors::gnu)
+    }
+
+    /// Parses a `LS_COLORS` value, as `di=01;34:ln=target:*.tar=01;31`.
+    pub fn from_ls_colors(value: &str) -> Result<Self, DircolorsError> {
+        let mut colors = LsColors::new();
+        for (index, entry) in value.split(':').enumerate() {
+            if entry.is_empty() {
+                continue;
+            }
+            let (key, sgr) = entry
+                .split_once('=')
+                .ok_or_else(|| DircolorsError::Syntax {
+                    line: index + 1,
+                    text: entry.to_owned(),
+                })?;
+            if let Some(suffix) = key.strip_prefix('*') {
+                colors.push_suffix(suffix, key, sgr)?;
+            } else if key == "ln" && sgr == "target" {
+                colors.link_target = true;
+            } else if let Some(indicator) = Indicator::from_code(key) {
+                colors.indicators.insert(indicator, parse_style(key, sgr)?);
+            } else if !ESCAPE_CODES.contains(&key) {
+                return Err(DircolorsError::UnknownKey(key.to_owned()));
+            }
+        }
+        Ok(colors)
+    }
+
+    /// Parses a `dircolors` database, with one `KEYWORD style`, `.ext style` or
+    /// `*suffix style` entry per line and `#` comments, for the terminal named by the
+    /// `TERM` and `COLORTERM` environment variables.
+    pub fn from_dircolors(database: &str) -> Result<Self, DircolorsError> {
+        let term = env::var("TERM")
+            .ok()
+            .filter(|term| !term.is_empty())
+            .unwrap_or_else(|| "none".to_owned());
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        LsColors::from_dircolors_for(database, &term, &colorterm)
+    }
+
+    /// Parses a `dircolors` database for a terminal, given its `TERM` and `COLORTERM`
+    /// values.
+    pub fn from_dircolors_for(
+        database: &str,
+        term: &str,
+        colorterm: &str,
+    ) -> Result<Self, DircolorsError> {
+        let mut colors = LsColors::new();
+        let mut section = Section::Global;
+        for (index, line) in database.lines().enumerate() {
+            let mut words = line
+                .split_whitespace()
+                .take_while(|word| !word.starts_with('#'));
+            let (key, sgr) = match (words.next(), words.next()) {
+                (None, _) => continue,
+                (Some(key), Some(sgr)) => (key, sgr),
+                (Some(_), None) => {
+                    return Err(DircolorsError::Syntax {
+                        line: index + 1,
+                        text: line.trim().to_owned(),
+                    })
+                }
+            };
+            let keyword = key.to_ascii_uppercase();
+            if keyword == "TERM" || keyword == "COLORTERM" {
+                let value = if keyword == "TERM" { term } else { colorterm };
+                if glob_match(sgr, value) {
+                    section = Section::Matched;
+                } else if section != Section::Matched {
+                    section = Section::Skipped;
+                }
+                continue;
+            }
+            if section == Section::Matched {
+                section = Section::Applied;
+            }
+            if section == Section::Skipped {
+                continue;
+            }
+            if key.starts_with('.') {
+                colors.push_suffix(key, key, sgr)?;
+            } else if let Some(suffix) = key.strip_prefix('*') {
+                colors.push_suffix(suffix, key, sgr)?;
+            } else if keyword == "EIGHTBIT" {
+                continue;
+            } else if (keyword == "LINK" || keyword == "SYMLINK")
+                && sgr.eq_ignore_ascii_case("target")
+            {
+                colors.link_target = true;
+            } else if let Some(indicator) = Indicator::from_keyword(&keyword) {
+                colors.indicators.insert(indicator, parse_style(key, sgr)?);
+            } else if !ESCAPE_KEYWORDS.contains(&keyword.as_str()) {
+                return Err(DircolorsError::UnknownKey(key.to_owned()));
+            }
+        }
+        Ok(colors)
+    }
+
+    /// Serializes the styles as a `LS_COLORS` value, as `dircolors` outputs it.
+    pub fn to_ls_colors(&self) -> String {
+        let mut entries = Vec::new();
+        for indicator in Indicator::ALL.iter() {
+            if *indicator == Indicator::Symlink && self.link_target {
+                entries.push("ln=target".to_owned());
+            } else if let Some(style) = self.indicators.get(indicator) {
+                entries.push(format!("{}={}", indicator.code(), sgr(style)));
+            }
+        }
+        for suffix in &self.suffixes {
+            entries.push(format!("*{}={}", suffix.suffix, sgr(&suffix.style)));
+        }
+        entries.join(":")
+    }
+
+    /// Sets the style of a file type.
+    pub fn set(mut self, indicator: Indicator, style: TextStyle) -> LsColors {
+        self.indicators.insert(indicator, style);
+        self
+    }
+
+    /// Sets the style of the file names ending with a suffix, such as `.tar`.
+    pub fn suffix(mut self, suffix: &str, style: TextStyle) -> LsColors {
+        self.insert_suffix(suffix, style);
+        self
+    }
+
+    /// Colors symbolic links as the files they point to, as `ln=target` does.
+    pub fn link_target(mut self) -> LsColors {
+        self.link_target = true;
+        self
+    }
+
+    /// Returns the style of a file type.
+    pub fn style(&self, indicator: Indicator) -> Option<TextStyle> {
+        self.indicators.get(&indicator).copied()
+    }
+
+    /// Returns the style of a regular file name from its suffix.
+    pub fn style_for_name(&self, name: &str) -> Option<TextStyle> {
+        self.suffixes
+            .iter()
+            .rev()
+            .find(|suffix| matches_suffix(name, suffix))
+            .map(|suffix| suffix.style)
+            .or_else(|| self.style(Indicator::File))
+    }
+
+    /// Returns the style of a file, from the metadata of the file itself, not of the
+    /// file a symbolic link points to, as returned by [`fs::symlink_metadata`].
+    pub fn style_for(&self, path: &Path, metadata: &Metadata) -> Option<TextStyle> {
+        let file_type = metadata.file_type();
+        if file_type.is_symlink() {
+            return match fs::metadata(path) {
+                Err(_) if self.has_style(Indicator::Orphan) => self.style(Indicator::Orphan),
+                Err(_) => self.style(Indicator::Symlink),
+                Ok(target) if self.link_target => match fs::canonicalize(path) {
+                    Ok(resolved) => self.style_for(&resolved, &target),
+                    Err(_) => self.style_for(path, &target),
+                },
+                Ok(_) => self.style(Indicator::Symlink),
+            };
+        }
+        let indicator = if file_type.is_dir() {
+            self.directory_indicator(metadata)
+        } else if file_type.is_file() {
+            self.file_indicator(metadata)
+        } else {
+            special_indicator(metadata)
+        };
+        match indicator {
+            Some(indicator) => self.style(indicator),
+            None => {
+                let name = path.file_name().map(|name| name.to_string_lossy());
+                self.style_for_name(name.as_deref().unwrap_or(""))
+            }
+        }
+    }
+
+    /// Returns the file name styled by its suffix, or unchanged if it has no style.
+    pub fn paint_name(&self, name: &str) -> String {
+        match self.style_for_name(name) {
+            Some(style) => style.render(name),
+            None => name.to_owned(),
+        }
+    }
+
+    /// Returns the text styled as the file at the path, reading its metadata, or
+    /// unchanged if the file has no style.
+    pub fn paint(&self, path: &Path, text: &str) -> String {
+        let style = match fs::symlink_metadata(path) {
+            Ok(metadata) => self.style_for(path, &metadata),
+            Err(_) => self.style(Indicator::Missing),
+        };
+        match style {
+            Some(style) => style.render(text),
+            None => text.to_owned(),
+        }
+    }
+
+    #[cfg(unix)]
+    fn directory_indicator(&self, metadata: &Metadata) -> Option<Indicator> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = metadata.permissions().mode();
+        let sticky = mode & 0o1000 != 0;
+        let writable = mode & 0o002 != 0;
+        let candidates = [
+            (sticky && writable, Indicator::StickyOtherWritable),
+            (writable, Indicator::OtherWritable),
+            (sticky, Indicator::Sticky),
+        ];
+        candidates
+            .iter()
+            .find(|(set, indicator)| *set && self.has_style(*indicator))
+            .map(|(_, indicator)| *indicator)
+            .or(Some(Indicator::Directory))
+    }
+
+    #[cfg(not(unix))]
+    fn directory_indicator(&self, _metadata: &Metadata) -> Option<Indicator> {
+        Some(Indicator::Directory)
+    }
+
+    /// Returns the indicator of a regular file, or `None` if it is styled by its name.
+    #[cfg(unix)]
+    fn file_indicator(&self, metadata: &Metadata) -> Option<Indicator> {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let mode = metadata.permissions().mode();
+        let candidates = [
+            (mode & 0o4000 != 0, Indicator::Setuid),
+            (mode & 0o2000 != 0, Indicator::Setgid),
+            (mode & 0o111 != 0, Indicator::Executable),
+            (metadata.nlink() > 1, Indicator::MultiHardLink),
+        ];
+        candidates
+            .iter()
+            .find(|(set, indicator)| *set && self.has_style(*indicator))
+            .map(|(_, indicator)| *indicator)
+    }
+
+    #[cfg(not(unix))]
+    fn file_indicator(&self, _metadata: &Metadata) -> Option<Indicator> {
+        None
+    }
+
+    /// Tells whether the indicator has a style which is not empty, `00` meaning the
+    /// rules of lower precedence apply.
+    fn has_style(&self, indicator: Indicator) -> bool {
+        self.style(indicator)
+            .is_some_and(|style| style != TextStyle::default())
+    }
+
+    fn push_suffix(&mut self, suffix: &str, key: &str, sgr: &str) -> Result<(), DircolorsError> {
+        let style = parse_style(key, sgr)?;
+        self.insert_suffix(suffix, style);
+        Ok(())
+    }
+
+    fn insert_suffix(&mut self, suffix: &str, style: TextStyle) {
+        let mut exact = false;
+        for other in self.suffixes.iter_mut() {
+            if other.suffix != suffix && other.suffix.eq_ignore_ascii_case(suffix) {
+                other.exact = true;
+                exact = true;
+            }
+        }
+        self.suffixes.push(Suffix {
+            suffix: suffix.to_owned(),
+            style,
+            exact,
+        });
+    }
+}
+
+/// Where a `dircolors` database line stands relative to its `TERM` and `COLORTERM` lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    /// Before any terminal line, the entries apply to every terminal.
+    Global,
+    /// In a run of terminal lines, one of which matched.
+    Matched,
+    /// After terminal lines which matched.
+    Applied,
+    /// After terminal lines none of which matched.
+    Skipped,
+}
+
+/// Returns the indicator of a fifo, socket or device.
+#[cfg(unix)]
+fn special_indicator(metadata: &Metadata) -> Option<Indicator> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let file_type = metadata.file_type();
+    if file_type.is_fifo() {
+        Some(Indicator::Fifo)
+    } else if file_type.is_socket() {
+        Some(Indicator::Socket)
+    } else if file_type.is_block_device() {
+        Some(Indicator::BlockDevice)
+    } else if file_type.is_char_device() {
+        Some(Indicator::CharDevice)
+    } else {
+        Some(Indicator::File)
+    }
+}
+
+#[cfg(not(unix))]
+fn special_indicator(_metadata: &Metadata) -> Option<Indicator> {
+    Some(Indicator::File)
+}
+
+fn matches_suffix(name: &str, suffix: &Suffix) -> bool {
+    if suffix.exact {
+        return name.ends_with(&suffix.suffix);
+    }
+    name.len() >= suffix.suffix.len()
+        && name.is_char_boundary(name.len() - suffix.suffix.len())
+        && name[name.len() - suffix.suffix.len()..].eq_ignore_ascii_case(&suffix.suffix)
+}
+
+/// Matches a text against a shell glob pattern, with `*`, `?` and `[...]` classes.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_from(&pattern, &text)
+}
+
+fn glob_match_from(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => (0..=text.len()).any(|skip| glob_match_from(&pattern[1..], &text[skip..])),
+        Some('?') => !text.is_empty() && glob_match_from(&pattern[1..], &text[1..]),
+        Some('[') => match (text.first(), class_end(pattern)) {
+            (Some(c), Some(end)) => {
+                class_matches(&pattern[1..end], *c)
+                    && glob_match_from(&pattern[end + 1..], &text[1..])
+            }
+            (None, _) => false,
+            (Some(c), None) => *c == '[' && glob_match_from(&pattern[1..], &text[1..]),
+        },
+        Some(c) => text.first() == Some(c) && glob_match_from(&pattern[1..], &text[1..]),
+    }
+}
+
+/// Returns the index of the `]` closing the class opening the pattern.
+fn class_end(pattern: &[char]) -> Option<usize> {
+    let mut index = 1;
+    if matches!(pattern.get(index), Some('!') | Some('^')) {
+        index += 1;
+    }
+    if pattern.get(index) == Some(&']') {
+        index += 1;
+    }
+    pattern[index..]
+        .iter()
+        .position(|c| *c == ']')
+        .map(|position| index + position)
+}
+
+/// Tells whether a character belongs to a class, given without its brackets.
+fn class_matches(class: &[char], c: char) -> bool {
+    let (negated, class) = match class.first() {
+        Some('!') | Some('^') => (true, &class[1..]),
+        _ => (false, class),
+    };
+    let mut index = 0;
+    let mut found = false;
+    while index < class.len() {
+        if index + 2 < class.len() && class[index + 1] == '-' {
+            found |= class[index] <= c && c <= class[index + 2];
+            index += 3;
+        } else {
+            found |= class[index] == c;
+            index += 1;
+        }
+    }
+    found != negated
+}
+
+fn parse_style(key: &str, sgr: &str) -> Result<TextStyle, DircolorsError> {
+    TextStyle::from_sgr(sgr).map_err(|error| DircolorsError::InvalidStyle {
+        key: key.to_owned(),
+        error,
+    })
+}
+
+/// Serializes a style for `LS_COLORS`, an empty style as `0`.
+fn sgr(style: &TextStyle) -> String {
+    let sgr = style.to_sgr();
+    if sgr.is_empty() {
+        "0".to_owned()
+    } else {
+        sgr
+    }
+}
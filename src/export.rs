@@ -0,0 +1,237 @@
+/*!
+# export
+This module writes a [`Palette`] in the formats of terminal emulators and design
+tools, so a scheme designed with this crate can be shipped: Xresources, kitty, Alacritty,
+Windows Terminal JSON, iTerm2 property lists, GIMP `.gpl` palettes and CSS custom
+properties. The roles of a [`Theme`] can be exported as CSS custom properties too.
+
+The default colors and the 16 system colors are always written. The entries 16 to 255
+are written only where they differ from the standard 256 color table, by the formats
+able to set them. Reading an exported scheme with the [`import`](crate::import) module
+gives back the same palette.
+
+# Examples
+
+```
+use rustcolor::export;
+use rustcolor::import;
+use rustcolor::palette::Palette;
+
+let palette = Palette::solarized_dark();
+let conf = export::kitty(&palette);
+assert!(conf.starts_with("foreground #839496\nbackground #002b36\ncolor0 #073642\n"));
+assert_eq!(palette, import::kitty(&conf).unwrap());
+```
+*/
+
+use crate::color::{RGB, SYSTEM_NAMES};
+use crate::palette::Palette;
+use crate::theme::Theme;
+use std::fmt::Write;
+
+/// Writes the palette as X resources, for any class.
+pub fn xresources(palette: &Palette) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "*.foreground: {}", palette.foreground);
+    let _ = writeln!(text, "*.background: {}", palette.background);
+    for (index, color) in colors(palette) {
+        let _ = writeln!(text, "*.color{}: {}", index, color);
+    }
+    text
+}
+
+/// Writes the palette as a kitty configuration, to include from `kitty.conf`.
+pub fn kitty(palette: &Palette) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "foreground {}", palette.foreground);
+    let _ = writeln!(text, "background {}", palette.background);
+    for (index, color) in colors(palette) {
+        let _ = writeln!(text, "color{} {}", index, color);
+    }
+    text
+}
+
+/// Writes the palette as the `colors` of an Alacritty TOML configuration.
+pub fn alacritty_toml(palette: &Palette) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "[colors.primary]");
+    let _ = writeln!(text, "foreground = \"{}\"", palette.foreground);
+    let _ = writeln!(text, "background = \"{}\"", palette.background);
+    for (table, offset) in [("normal", 0), ("bright", 8)] {
+        let _ = writeln!(text, "\n[colors.{}]", table);
+        for (index, name) in SYSTEM_NAMES.iter().enumerate() {
+            let _ = writeln!(text, "{} = \"{}\"", name, palette.colors[offset + index]);
+        }
+    }
+    for (index, color) in palette.overrides() {
+        let _ = writeln!(text, "\n[[colors.indexed_colors]]");
+        let _ = writeln!(text, "index = {}", index);
+        let _ = writeln!(text, "color = \"{}\"", color);
+    }
+    text
+}
+
+/// Writes the palette as a Windows Terminal color scheme, to add to the `schemes` of
+/// its settings.
+pub fn windows_terminal(palette: &Palette, name: &str) -> String {
+    let mut members = vec![
+        ("name".to_owned(), json_string(name)),
+        ("foreground".to_owned(), json_string(&palette.foreground)),
+        ("background".to_owned(), json_string(&palette.background)),
+    ];
+    for (index, ansi) in SYSTEM_NAMES.iter().enumerate() {
+        let ansi = if *ansi == "magenta" { "purple" } else { ansi };
+        let bright = format!("bright{}{}", ansi[..1].to_ascii_uppercase(), &ansi[1..]);
+        members.push((ansi.to_owned(), json_string(&palette.colors[index])));
+        members.push((bright, json_string(&palette.colors[index + 8])));
+    }
+    let members: Vec<String> = members
+        .iter()
+        .map(|(key, value)| format!("    \"{}\": {}", key, value))
+        .collect();
+    format!("{{\n{}\n}}\n", members.join(",\n"))
+}
+
+/// Writes the palette as an iTerm2 `.itermcolors` property list, in the sRGB color space.
+pub fn iterm2(palette: &Palette) -> String {
+    let mut text = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+         \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n<dict>\n",
+    );
+    let mut entries = vec![
+        ("Background Color".to_owned(), palette.background),
+        ("Foreground Color".to_owned(), palette.foreground),
+    ];
+    for (index, color) in palette.colors[..16].iter().enumerate() {
+        entries.push((format!("Ansi {} Color", index), *color));
+    }
+    for (name, RGB(r, g, b)) in entries {
+        let _ = writeln!(text, "\t<key>{}</key>\n\t<dict>", name);
+        let _ = writeln!(text, "\t\t<key>Alpha Component</key>\n\t\t<real>1</real>");
+        let _ = writeln!(
+            text,
+            "\t\t<key>Blue Component</key>\n\t\t<real>{}</real>",
+            unit(b)
+        );
+        let _ = writeln!(
+            text,
+            "\t\t<key>Color Space</key>\n\t\t<string>sRGB</string>"
+        );
+        let _ = writeln!(
+            text,
+            "\t\t<key>Green Component</key>\n\t\t<real>{}</real>",
+            unit(g)
+        );
+        let _ = writeln!(
+            text,
+            "\t\t<key>Red Component</key>\n\t\t<real>{}</real>",
+            unit(r)
+        );
+        let _ = writeln!(text, "\t</dict>");
+    }
+    text.push_str("</dict>\n</plist>\n");
+    text
+}
+
+/// Writes the palette as a GIMP `.gpl` palette, also read by Inkscape and Krita.
+pub fn gimp(palette: &Palette, name: &str) -> String {
+    let mut text = format!("GIMP Palette\nName: {}\nColumns: 8\n#\n", name);
+    let mut entry = |label: &str, RGB(r, g, b): RGB| {
+        let _ = writeln!(text, "{:3} {:3} {:3}\t{}", r, g, b, label);
+    };
+    entry("foreground", palette.foreground);
+    entry("background", palette.background);
+    for (index, color) in colors(palette) {
+        let label = match index {
+            0..=7 => SYSTEM_NAMES[index as usize].to_owned(),
+            8..=15 => format!("bright {}", SYSTEM_NAMES[index as usize - 8]),
+            _ => format!("color {}", index),
+        };
+        entry(&label, color);
+    }
+    text
+}
+
+/// Writes the palette as CSS custom properties of the root element, such as
+/// `--ansi-foreground` and `--ansi-1`.
+pub fn css(palette: &Palette) -> String {
+    let mut text = String::from(":root {\n");
+    let _ = writeln!(text, "  --ansi-foreground: {};", palette.foreground);
+    let _ = writeln!(text, "  --ansi-background: {};", palette.background);
+    for (index, color) in colors(palette) {
+        let _ = writeln!(text, "  --ansi-{}: {};", index, color);
+    }
+    text.push_str("}\n");
+    text
+}
+
+/// Writes the styles of a theme as CSS custom properties of the root element, such
+/// as `--warn-color` and `--warn-font-weight`, resolving their colors with the palette.
+/// Default colors and attributes without a CSS equivalent are left out.
+pub fn theme_css(theme: &Theme, palette: &Palette) -> String {
+    let mut text = String::from(":root {\n");
+    for (name, style) in theme.styles() {
+        if let Some(color) = style.foreground.and_then(|color| palette.resolve(color)) {
+            let _ = writeln!(text, "  --{}-color: {};", name, color);
+        }
+        if let Some(color) = style.background.and_then(|color| palette.resolve(color)) {
+            let _ = writeln!(text, "  --{}-background-color: {};", name, color);
+        }
+        if style.bold {
+            let _ = writeln!(text, "  --{}-font-weight: bold;", name);
+        }
+        if style.italic {
+            let _ = writeln!(text, "  --{}-font-style: italic;", name);
+        }
+        let decorations: Vec<&str> = [
+            (style.underline, "underline"),
+            (style.strikethrough, "line-through"),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, decoration)| *decoration)
+        .collect();
+        if !decorations.is_empty() {
+            let _ = writeln!(
+                text,
+                "  --{}-text-decoration: {};",
+                name,
+                decorations.join(" ")
+            );
+        }
+    }
+    text.push_str("}\n");
+    text
+}
+
+/// Returns the 16 system colors followed by the entries differing from the standard
+/// 256 color table.
+fn colors(palette: &Palette) -> impl Iterator<Item = (u8, RGB)> + '_ {
+    (0..16)
+        .map(move |index| (index, palette.colors[index as usize]))
+        .chain(palette.overrides())
+}
+
+/// Returns a color component between 0 and 1, with enough digits to read it back exactly.
+fn unit(component: u8) -> f64 {
+    component as f64 / 255.0
+}
+
+/// Quotes a JSON string.
+fn json_string<T: ToString + ?Sized>(value: &T) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.to_string().chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
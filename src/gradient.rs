@@ -0,0 +1,198 @@
+/*!
+# gradient
+This module implements color gradients with multiple stops, for progress bars and
+heatmaps. Colors between two stops are interpolated in a selectable space: plain
+sRGB, linear sRGB, OKLab, which keeps the perceived lightness even, or HSL along the
+shortest path around the hue circle.
+
+# Examples
+
+```
+use rustcolor::color::RGB;
+use rustcolor::gradient::{Gradient, Interpolation};
+
+let gradient = Gradient::new(&[RGB(255, 0, 0), RGB(0, 0, 255)]);
+assert_eq!(vec![RGB(255, 0, 0), RGB(128, 0, 128), RGB(0, 0, 255)], gradient.sample(3));
+
+let rainbow = Gradient::new(&[RGB(255, 0, 0), RGB(0, 0, 255)]).interpolation(Interpolation::Hsl);
+assert_eq!(RGB(255, 0, 255), rainbow.at(0.5));
+
+println!("{}", rainbow.paint("a rainbow colored text"));
+```
+*/
+
+use crate::color::{Color, RGB};
+use crate::space::{from_unit, unit, Hsl, LinearRgb, Oklab};
+use crate::style::TextStyle;
+
+/// The space in which colors between two stops are interpolated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Interpolates the gamma encoded components, as most tools do.
+    Srgb,
+    /// Interpolates the light intensities, as a physical mix of lights.
+    LinearRgb,
+    /// Interpolates in OKLab, with perceptually even steps.
+    Oklab,
+    /// Interpolates in HSL, along the shortest path around the hue circle.
+    Hsl,
+}
+
+/// A gradient made of color stops placed in 0.0-1.0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f64, RGB)>,
+    interpolation: Interpolation,
+}
+
+impl Gradient {
+    /// Creates a gradient of evenly spaced colors, interpolated in sRGB.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `colors` is empty.
+    pub fn new(colors: &[RGB]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f64;
+        let stops: Vec<(f64, RGB)> = colors
+            .iter()
+            .enumerate()
+            .map(|(index, color)| (index as f64 / last, *color))
+            .collect();
+        Gradient::with_stops(&stops)
+    }
+
+    /// Creates a gradient from colors at given positions, which are clamped into 0.0-1.0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stops` is empty.
+    pub fn with_stops(stops: &[(f64, RGB)]) -> Self {
+        assert!(!stops.is_empty(), "a gradient needs at least one color");
+        let mut stops: Vec<(f64, RGB)> = stops
+            .iter()
+            .map(|(position, color)| (position.clamp(0.0, 1.0), *color))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            stops,
+            interpolation: Interpolation::Srgb,
+        }
+    }
+
+    /// Sets the interpolation space.
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Gradient {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Returns the color at a position in 0.0-1.0.
+    pub fn at(&self, position: f64) -> RGB {
+        let position = position.clamp(0.0, 1.0);
+        let next = self
+            .stops
+            .iter()
+            .position(|(stop, _)| *stop > position)
+            .unwrap_or(self.stops.len());
+        if next == 0 {
+            return self.stops[0].1;
+        }
+        if next == self.stops.len() {
+            return self.stops[next - 1].1;
+        }
+        let (start, from) = self.stops[next - 1];
+        let (end, to) = self.stops[next];
+        mix(
+            from,
+            to,
+            (position - start) / (end - start),
+            self.interpolation,
+        )
+    }
+
+    /// Returns `count` evenly spaced colors, from the first to the last stop.
+    pub fn sample(&self, count: usize) -> Vec<RGB> {
+        let last = count.saturating_sub(1).max(1) as f64;
+        (0..count)
+            .map(|index| self.at(index as f64 / last))
+            .collect()
+    }
+
+    /// Colors the characters of a text with the gradient, as foreground.
+    pub fn paint(&self, text: &str) -> String {
+        self.paint_with(text, |color| TextStyle {
+            foreground: Some(Color::Rgb(color)),
+            ..TextStyle::default()
+        })
+    }
+
+    /// Colors the characters of a text with the gradient, as background.
+    pub fn paint_background(&self, text: &str) -> String {
+        self.paint_with(text, |color| TextStyle {
+            background: Some(Color::Rgb(color)),
+            ..TextStyle::default()
+        })
+    }
+
+    fn paint_with(&self, text: &str, style: impl Fn(RGB) -> TextStyle) -> String {
+        let characters: Vec<char> = text.chars().collect();
+        let colors = self.sample(characters.len());
+        let mut painted = String::new();
+        let mut run = String::new();
+        for (index, character) in characters.iter().enumerate() {
+            run.push(*character);
+            if colors.get(index + 1) != Some(&colors[index]) {
+                painted.push_str(&style(colors[index]).render(&run));
+                run.clear();
+            }
+        }
+        painted
+    }
+}
+
+/// Interpolates between two colors, `amount` being in 0.0-1.0.
+pub fn mix(from: RGB, to: RGB, amount: f64, interpolation: Interpolation) -> RGB {
+    let lerp = |a: f64, b: f64| a + (b - a) * amount;
+    match interpolation {
+        Interpolation::Srgb => {
+            let (fr, fg, fb) = unit(from);
+            let (tr, tg, tb) = unit(to);
+            from_unit(lerp(fr, tr), lerp(fg, tg), lerp(fb, tb))
+        }
+        Interpolation::LinearRgb => {
+            let (from, to) = (LinearRgb::from(from), LinearRgb::from(to));
+            RGB::from(LinearRgb {
+                r: lerp(from.r, to.r),
+                g: lerp(from.g, to.g),
+                b: lerp(from.b, to.b),
+            })
+        }
+        Interpolation::Oklab => {
+            let (from, to) = (Oklab::from(from), Oklab::from(to));
+            RGB::from(Oklab {
+                l: lerp(from.l, to.l),
+                a: lerp(from.a, to.a),
+                b: lerp(from.b, to.b),
+            })
+        }
+        Interpolation::Hsl => {
+            let (mut from, mut to) = (Hsl::from(from), Hsl::from(to));
+            if from.saturation == 0.0 {
+                from.hue = to.hue;
+            }
+            if to.saturation == 0.0 {
+                to.hue = from.hue;
+            }
+            let mut delta = to.hue - from.hue;
+            if delta > 180.0 {
+                delta -= 360.0;
+            } else if delta < -180.0 {
+                delta += 360.0;
+            }
+            RGB::from(Hsl::new(
+                from.hue + delta * amount,
+                lerp(from.saturation, to.saturation),
+                lerp(from.lightness, to.lightness),
+            ))
+        }
+    }
+}
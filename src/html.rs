@@ -0,0 +1,251 @@
+/*!
+# html
+This module converts ANSI styled text into HTML, so terminal output such as CI logs
+can be published on a web page. Each styled run of text becomes a `span`, styled
+either with inline CSS or with CSS classes described by [`HtmlConverter::stylesheet`].
+16 and 256 colors are mapped to RGB through a [`Palette`], while 24bit colors are
+always written inline.
+
+Browsers do not render `text-decoration: blink`, so blinking text is animated in both
+modes, with the `@keyframes` rule of the stylesheet: a page showing blinking text
+includes [`HtmlConverter::stylesheet`] even with inline styles.
+
+# Examples
+
+```
+use rustcolor::html::HtmlConverter;
+use rustcolor::printer::ColorPrinter;
+
+let html = HtmlConverter::new().convert(&"<error>".error());
+assert_eq!(
+    "<span style=\"color:#e5e5e5;background-color:#cd0000\">&lt;error&gt;</span>",
+    html
+);
+
+let html = HtmlConverter::new().classes().convert(&"<error>".error());
+assert_eq!(
+    "<span class=\"ansi-fg-7 ansi-bg-1\">&lt;error&gt;</span>",
+    html
+);
+```
+*/
+
+use crate::color::Color;
+use crate::palette::Palette;
+use crate::parser::{parse, Token};
+use crate::style::TextStyle;
+
+const DEFAULT_CLASS_PREFIX: &str = "ansi-";
+const BLINK_ANIMATION: &str = "1s steps(1) infinite";
+
+/// Converts ANSI styled text into HTML.
+pub struct HtmlConverter {
+    palette: Palette,
+    classes: bool,
+    class_prefix: String,
+}
+
+impl HtmlConverter {
+    /// Creates a converter writing inline styles with the default palette.
+    pub fn new() -> Self {
+        Self {
+            palette: Palette::default(),
+            classes: false,
+            class_prefix: DEFAULT_CLASS_PREFIX.to_owned(),
+        }
+    }
+
+    /// Sets the palette used to map 16 and 256 colors to RGB.
+    pub fn palette(mut self, palette: Palette) -> HtmlConverter {
+        self.palette = palette;
+        self
+    }
+
+    /// Writes CSS classes instead of inline styles.
+    pub fn classes(mut self) -> HtmlConverter {
+        self.classes = true;
+        self
+    }
+
+    /// Sets the prefix of the CSS classes, `ansi-` by default.
+    pub fn class_prefix(mut self, prefix: &str) -> HtmlConverter {
+        self.class_prefix = prefix.to_owned();
+        self
+    }
+
+    /// Converts ANSI styled text into an HTML fragment. Line breaks are kept,
+    /// so the fragment is meant to be placed in a `pre` element.
+    pub fn convert(&self, input: &str) -> String {
+        let mut html = String::new();
+        let mut style = TextStyle::default();
+        for token in parse(input) {
+            match token {
+                Token::Text(text) => self.push_span(&mut html, &style, &text),
+                Token::Control(b'\n') => html.push('\n'),
+                Token::Control(b'\t') => html.push('\t'),
+                Token::Sgr(changes) => changes.iter().for_each(|change| style.apply(change)),
+                _ => {}
+            }
+        }
+        html
+    }
+
+    /// Converts ANSI styled text into a `pre` element, with the palette default colors.
+    pub fn convert_pre(&self, input: &str) -> String {
+        format!(
+            "<pre style=\"color:{};background-color:{}\">{}</pre>",
+            self.palette.foreground,
+            self.palette.background,
+            self.convert(input)
+        )
+    }
+
+    /// Returns the stylesheet defining the classes written in classes mode, and the
+    /// blink animation of both modes.
+    pub fn stylesheet(&self) -> String {
+        let prefix = &self.class_prefix;
+        let mut css = String::new();
+        for (index, color) in self.palette.colors.iter().enumerate() {
+            css.push_str(&format!(
+                ".{}fg-{} {{ color: {}; }}\n",
+                prefix, index, color
+            ));
+            css.push_str(&format!(
+                ".{}bg-{} {{ background-color: {}; }}\n",
+                prefix, index, color
+            ));
+        }
+        css.push_str(&format!(".{}bold {{ font-weight: bold; }}\n", prefix));
+        css.push_str(&format!(".{}faint {{ opacity: 0.5; }}\n", prefix));
+        css.push_str(&format!(".{}italic {{ font-style: italic; }}\n", prefix));
+        css.push_str(&format!(
+            ".{}underline {{ text-decoration: underline; }}\n",
+            prefix
+        ));
+        css.push_str(&format!(
+            ".{}strikethrough {{ text-decoration: line-through; }}\n",
+            prefix
+        ));
+        css.push_str(&format!(
+            ".{0}underline.{0}strikethrough {{ text-decoration: underline line-through; }}\n",
+            prefix
+        ));
+        css.push_str(&format!(".{}hidden {{ color: transparent; }}\n", prefix));
+        css.push_str(&format!(
+            "@keyframes {}blink {{ 50% {{ opacity: 0; }} }}\n",
+            prefix
+        ));
+        css.push_str(&format!(
+            ".{0}blink {{ animation: {0}blink {1}; }}\n",
+            prefix, BLINK_ANIMATION
+        ));
+        css
+    }
+
+    fn push_span(&self, html: &mut String, style: &TextStyle, text: &str) {
+        let (foreground, background) = self.colors(style);
+        let mut classes = Vec::new();
+        let mut declarations = Vec::new();
+        let mut decorations = Vec::new();
+
+        for (color, class, property) in [
+            (foreground, "fg", "color"),
+            (background, "bg", "background-color"),
+        ]
+        .iter()
+        {
+            match color {
+                Some(Color::C16(n)) if self.classes => {
+                    classes.push(format!("{}{}-{}", self.class_prefix, class, n & 15))
+                }
+                Some(Color::C256(n)) if self.classes => {
+                    classes.push(format!("{}{}-{}", self.class_prefix, class, n))
+                }
+                Some(color) => {
+                    if let Some(rgb) = self.palette.resolve(*color) {
+                        declarations.push(format!("{}:{}", property, rgb));
+                    }
+                }
+                None => {}
+            }
+        }
+
+        let blink = format!("animation:{}blink {}", self.class_prefix, BLINK_ANIMATION);
+        let attributes = [
+            (style.bold, "bold", "font-weight:bold"),
+            (style.faint, "faint", "opacity:0.5"),
+            (style.italic, "italic", "font-style:italic"),
+            (style.underline, "underline", ""),
+            (style.strikethrough, "strikethrough", ""),
+            (style.blink, "blink", blink.as_str()),
+            (style.hidden, "hidden", "color:transparent"),
+        ];
+        for (enabled, class, declaration) in attributes.iter() {
+            if !enabled {
+                continue;
+            }
+            if self.classes {
+                classes.push(format!("{}{}", self.class_prefix, class));
+            } else if declaration.is_empty() {
+                decorations.push(match *class {
+                    "strikethrough" => "line-through",
+                    class => class,
+                });
+            } else {
+                declarations.push((*declaration).to_owned());
+            }
+        }
+        if !decorations.is_empty() {
+            declarations.push(format!("text-decoration:{}", decorations.join(" ")));
+        }
+
+        let mut attributes = String::new();
+        if !classes.is_empty() {
+            attributes.push_str(&format!(" class=\"{}\"", classes.join(" ")));
+        }
+        if !declarations.is_empty() {
+            attributes.push_str(&format!(" style=\"{}\"", declarations.join(";")));
+        }
+
+        if attributes.is_empty() {
+            html.push_str(&escape(text));
+        } else {
+            html.push_str(&format!("<span{}>{}</span>", attributes, escape(text)));
+        }
+    }
+
+    /// Returns the displayed foreground and background, swapped by reverse video.
+    fn colors(&self, style: &TextStyle) -> (Option<Color>, Option<Color>) {
+        let foreground = style.foreground.filter(|color| *color != Color::Default);
+        let background = style.background.filter(|color| *color != Color::Default);
+        if !style.reverse {
+            return (foreground, background);
+        }
+        (
+            Some(background.unwrap_or(Color::Rgb(self.palette.background))),
+            Some(foreground.unwrap_or(Color::Rgb(self.palette.foreground))),
+        )
+    }
+}
+
+impl Default for HtmlConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Escapes the HTML special characters of a text.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,207 @@
+/*!
+# hyperlink
+This module makes text clickable with the OSC 8 escape sequence, supported by most
+modern terminals: **ESC]8;params;URI ESC\\** opens a link, and the same sequence with
+an empty URI closes it. Links can carry an `id`, so a terminal underlines together the
+parts of a link split across lines or redrawn.
+
+Terminals which do not support OSC 8 usually ignore it, but some print it, so links
+are only written when [`enabled`] says the terminal supports them, and the text is
+left plain otherwise. The links are removed by [`strip`](crate::parser::strip) and do
+not count in [`width`](crate::parser::width).
+
+# Examples
+
+```
+use rustcolor::hyperlink::Hyperlink;
+use rustcolor::parser;
+use rustcolor::printer::ColorPrinter;
+
+let link = Hyperlink::new("https://example.com").id("docs");
+let output = link.wrap(&"docs".info());
+assert_eq!(
+    "\u{001b}]8;id=docs;https://example.com\u{001b}\\\u{001b}[32;49mdocs\u{001b}[0m\u{001b}]8;;\u{001b}\\",
+    output
+);
+assert_eq!("docs", parser::strip(&output));
+assert_eq!(4, parser::width(&output));
+
+// written only where the terminal supports it
+println!("see {}", "the docs".hyperlink("https://example.com"));
+```
+*/
+
+use std::env;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+const OSC_8: &str = "\u{001b}]8;";
+const ST: &str = "\u{001b}\\";
+const AUTO: u8 = 0;
+const ON: u8 = 1;
+const OFF: u8 = 2;
+
+/// The terminals known to support OSC 8, by their `TERM_PROGRAM`.
+const TERM_PROGRAMS: [&str; 7] = [
+    "iTerm.app",
+    "WezTerm",
+    "vscode",
+    "Hyper",
+    "ghostty",
+    "Tabby",
+    "rio",
+];
+/// The terminals known to support OSC 8, by their `TERM`.
+const TERMS: [&str; 5] = [
+    "xterm-kitty",
+    "alacritty",
+    "foot",
+    "xterm-ghostty",
+    "wezterm",
+];
+
+static MODE: AtomicU8 = AtomicU8::new(AUTO);
+static DETECTED: OnceLock<bool> = OnceLock::new();
+
+/// A link target, written around a span of text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hyperlink {
+    url: String,
+    id: Option<String>,
+}
+
+impl Hyperlink {
+    /// Creates a link to an URL.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_owned(),
+            id: None,
+        }
+    }
+
+    /// Creates a `file://` link to a path, made absolute from the current directory.
+    /// A Windows drive is written as `file:///C:/...`.
+    pub fn file(path: &Path) -> Self {
+        let absolute = env::current_dir()
+            .map(|directory| directory.join(path))
+            .unwrap_or_else(|_| path.to_path_buf());
+        let mut text = absolute.to_string_lossy().replace('\\', "/");
+        if !text.starts_with('/') {
+            text.insert(0, '/');
+        }
+        let mut url = String::from("file://");
+        for byte in text.bytes() {
+            if byte.is_ascii_alphanumeric() || b"/-._~:".contains(&byte) {
+                url.push(byte as char);
+            } else {
+                url.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        Hyperlink::new(&url)
+    }
+
+    /// Sets the id grouping the spans of the same link.
+    pub fn id(mut self, id: &str) -> Hyperlink {
+        self.id = Some(id.to_owned());
+        self
+    }
+
+    /// Returns the URL of the link.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the sequence opening the link. Characters not allowed in the sequence
+    /// are percent encoded.
+    pub fn open(&self) -> String {
+        let params = match &self.id {
+            Some(id) => format!(
+                "id={}",
+                encode(id, |byte| byte > b' ' && !b":;=".contains(&byte))
+            ),
+            None => String::new(),
+        };
+        let url = encode(&self.url, |byte| byte > b' ');
+        format!("{}{};{}{}", OSC_8, params, url, ST)
+    }
+
+    /// Returns the sequence closing any link.
+    pub fn close() -> String {
+        format!("{};{}", OSC_8, ST)
+    }
+
+    /// Writes the link around the text, styled or not, whether the terminal supports
+    /// links or not.
+    pub fn wrap(&self, text: &str) -> String {
+        format!("{}{}{}", self.open(), text, Hyperlink::close())
+    }
+
+    /// Writes the link around the text if links are [`enabled`], or returns the text
+    /// unchanged.
+    pub fn render(&self, text: &str) -> String {
+        if enabled() {
+            self.wrap(text)
+        } else {
+            text.to_owned()
+        }
+    }
+}
+
+/// Forces links on or off for the whole process, or restores the detection with `None`.
+pub fn set_enabled(enabled: Option<bool>) {
+    let mode = match enabled {
+        None => AUTO,
+        Some(true) => ON,
+        Some(false) => OFF,
+    };
+    MODE.store(mode, Ordering::Relaxed);
+}
+
+/// Tells whether links are written, as set by [`set_enabled`] or else detected once
+/// with [`supported`].
+pub fn enabled() -> bool {
+    match MODE.load(Ordering::Relaxed) {
+        ON => true,
+        OFF => false,
+        _ => *DETECTED.get_or_init(supported),
+    }
+}
+
+/// Detects whether the standard output is a terminal supporting links, from the
+/// environment variables terminals set. `FORCE_HYPERLINK=1` or `0` overrides it.
+pub fn supported() -> bool {
+    if let Ok(force) = env::var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+    let set = |name: &str| env::var_os(name).is_some();
+    if set("DOMTERM") || set("WT_SESSION") || set("KONSOLE_VERSION") {
+        return true;
+    }
+    let vte = env::var("VTE_VERSION")
+        .ok()
+        .and_then(|version| version.parse::<u32>().ok());
+    if vte.is_some_and(|version| version >= 5000) {
+        return true;
+    }
+    let program = env::var("TERM_PROGRAM").unwrap_or_default();
+    let term = env::var("TERM").unwrap_or_default();
+    TERM_PROGRAMS.contains(&program.as_str()) || TERMS.contains(&term.as_str())
+}
+
+/// Percent encodes the bytes not accepted by the predicate.
+fn encode(text: &str, accepted: impl Fn(u8) -> bool) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii() && accepted(byte) && byte != 0x7f {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
@@ -0,0 +1,1037 @@
+/*!
+# import
+This module reads the color schemes of popular terminal emulators into a [`Palette`],
+so the output of a program can match the scheme its user already chose: iTerm2
+`.itermcolors` property lists, Windows Terminal JSON schemes, Alacritty TOML and YAML
+configurations, kitty `.conf` files, Xresources and base16 or base24 YAML schemes.
+
+Terminal configurations usually set only some of the colors, so the entries a scheme
+leaves out keep their xterm values. A base16 scheme has no terminal colors of its own,
+they are derived from its 16 or 24 base colors as the base16 shell templates do.
+
+# Examples
+
+```
+use rustcolor::color::{Color, RGB};
+use rustcolor::import;
+
+let palette = import::kitty(
+    "# Tomorrow Night\n\
+     foreground #c5c8c6\n\
+     background #1d1f21\n\
+     color1     #cc6666\n",
+)
+.unwrap();
+
+assert_eq!(RGB(29, 31, 33), palette.background);
+assert_eq!(Some(RGB(204, 102, 102)), palette.resolve(Color::C16(1)));
+assert_eq!(Some(RGB(0, 205, 0)), palette.resolve(Color::C16(2)));
+```
+*/
+
+use crate::color::{RGB, SYSTEM_NAMES};
+use crate::names;
+use crate::palette::Palette;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The base16 colors of the 16 terminal colors.
+const BASE16_SLOTS: [&str; 16] = [
+    "base00", "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base05", "base03",
+    "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base07",
+];
+/// The base24 colors of the 16 terminal colors, with distinct bright colors.
+const BASE24_SLOTS: [&str; 16] = [
+    "base00", "base08", "base0B", "base0A", "base0D", "base0E", "base0C", "base06", "base02",
+    "base12", "base14", "base13", "base16", "base17", "base15", "base07",
+];
+
+/// The scheme formats which can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// iTerm2 `.itermcolors` property lists.
+    ITerm2,
+    /// Windows Terminal color schemes, alone or in a `settings.json`.
+    WindowsTerminal,
+    /// Alacritty TOML configurations.
+    AlacrittyToml,
+    /// Alacritty YAML configurations, before version 0.13.
+    AlacrittyYaml,
+    /// kitty `.conf` files.
+    Kitty,
+    /// X resources, as loaded by `xrdb`.
+    Xresources,
+    /// base16 and base24 YAML schemes.
+    Base16,
+}
+
+impl Format {
+    /// Guesses the format of a scheme from its file name, and from its content for
+    /// YAML files, which can be Alacritty configurations or base16 schemes.
+    pub fn detect(path: &Path, text: &str) -> Option<Format> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        match name.rsplit('.').next()? {
+            "itermcolors" => Some(Format::ITerm2),
+            "json" => Some(Format::WindowsTerminal),
+            "toml" => Some(Format::AlacrittyToml),
+            "yml" | "yaml" => {
+                let base16 = text.lines().any(|line| {
+                    let line = line.trim_start().to_ascii_lowercase();
+                    line.starts_with("base00:") || line.starts_with("\"base00\":")
+                });
+                if base16 {
+                    Some(Format::Base16)
+                } else {
+                    Some(Format::AlacrittyYaml)
+                }
+            }
+            "conf" => Some(Format::Kitty),
+            "xresources" | "xdefaults" | "xrdb" | "ad" => Some(Format::Xresources),
+            _ => None,
+        }
+    }
+}
+
+/// The reasons a scheme can fail to import.
+#[derive(Debug)]
+pub enum ImportError {
+    /// Reading the scheme file failed.
+    Io(io::Error),
+    /// The format of the scheme file could not be guessed from its name.
+    UnknownFormat(String),
+    /// The scheme is malformed, at the given line.
+    Syntax { line: usize, message: String },
+    /// The value of a color entry is not a color.
+    InvalidColor { key: String, value: String },
+    /// A color the format requires is missing.
+    MissingColor(String),
+    /// The scheme does not set any color.
+    NoColors,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(error) => write!(f, "cannot read the scheme: {}", error),
+            ImportError::UnknownFormat(path) => write!(f, "unknown scheme format '{}'", path),
+            ImportError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ImportError::InvalidColor { key, value } => {
+                write!(f, "invalid color '{}' for '{}'", value, key)
+            }
+            ImportError::MissingColor(key) => write!(f, "missing color '{}'", key),
+            ImportError::NoColors => write!(f, "the scheme does not set any color"),
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(error: io::Error) -> Self {
+        ImportError::Io(error)
+    }
+}
+
+/// Reads a scheme file, guessing its format with [`Format::detect`].
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Palette, ImportError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+    let format = Format::detect(path, &text)
+        .ok_or_else(|| ImportError::UnknownFormat(path.display().to_string()))?;
+    parse(&text, format)
+}
+
+/// Reads a scheme in the given format.
+pub fn parse(text: &str, format: Format) -> Result<Palette, ImportError> {
+    match format {
+        Format::ITerm2 => iterm2(text),
+        Format::WindowsTerminal => windows_terminal(text),
+        Format::AlacrittyToml => alacritty_toml(text),
+        Format::AlacrittyYaml => alacritty_yaml(text),
+        Format::Kitty => kitty(text),
+        Format::Xresources => xresources(text),
+        Format::Base16 => base16(text),
+    }
+}
+
+/// Reads an iTerm2 `.itermcolors` property list. The color space of the colors is
+/// ignored, their components are taken as sRGB.
+pub fn iterm2(text: &str) -> Result<Palette, ImportError> {
+    let mut builder = Builder::new();
+    let mut elements: Vec<&str> = Vec::new();
+    let mut key: Option<&str> = None;
+    let mut entry: Option<&str> = None;
+    let mut components: [Option<f64>; 3] = [None; 3];
+    for (line, token) in xml_tokens(text)? {
+        let dicts = elements.iter().filter(|name| **name == "dict").count();
+        match token {
+            Xml::Open(name) => {
+                if name == "dict" && dicts == 1 {
+                    entry = key.take();
+                    components = [None; 3];
+                }
+                elements.push(name);
+            }
+            Xml::Close(name) => {
+                if elements.pop() != Some(name) {
+                    return Err(ImportError::Syntax {
+                        line,
+                        message: format!("unexpected closing tag '{}'", name),
+                    });
+                }
+                if name != "dict" || dicts != 2 {
+                    continue;
+                }
+                let slot = match entry.and_then(iterm2_slot) {
+                    Some(slot) => slot,
+                    None => continue,
+                };
+                let mut rgb = [0; 3];
+                for (component, value) in rgb.iter_mut().zip(components.iter()) {
+                    let value = value.ok_or_else(|| {
+                        ImportError::MissingColor(format!("{} components", entry.unwrap_or("")))
+                    })?;
+                    *component = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+                builder.put(slot, RGB(rgb[0], rgb[1], rgb[2]));
+            }
+            Xml::Text(text) => match elements.last() {
+                Some(&"key") => key = Some(text),
+                Some(&"real") | Some(&"integer") if dicts == 2 => {
+                    let index = match key {
+                        Some("Red Component") => 0,
+                        Some("Green Component") => 1,
+                        Some("Blue Component") => 2,
+                        _ => continue,
+                    };
+                    let value = text.parse().map_err(|_| ImportError::InvalidColor {
+                        key: format!("{} {}", entry.unwrap_or(""), key.unwrap_or("")),
+                        value: text.to_owned(),
+                    })?;
+                    components[index] = Some(value);
+                }
+                _ => {}
+            },
+        }
+    }
+    builder.finish()
+}
+
+/// Reads a Windows Terminal color scheme. A `settings.json` is accepted too, the first
+/// of its `schemes` is read.
+pub fn windows_terminal(text: &str) -> Result<Palette, ImportError> {
+    let root = JsonParser::new(text).document()?;
+    let scheme = match root.member("schemes") {
+        Some(Json::Array(schemes)) => schemes.first().ok_or(ImportError::NoColors)?,
+        _ => &root,
+    };
+    let members = match scheme {
+        Json::Object(members) => members,
+        _ => return Err(ImportError::NoColors),
+    };
+    let mut builder = Builder::new();
+    for (key, value) in members {
+        let slot = match key.as_str() {
+            "foreground" => Slot::Foreground,
+            "background" => Slot::Background,
+            key => match key.strip_prefix("bright") {
+                Some(name) => match ansi_index(&name.to_ascii_lowercase()) {
+                    Some(index) => Slot::Color(index + 8),
+                    None => continue,
+                },
+                None => match ansi_index(key) {
+                    Some(index) => Slot::Color(index),
+                    None => continue,
+                },
+            },
+        };
+        match value {
+            Json::String(color) => builder.set(slot, key, color)?,
+            _ => {
+                return Err(ImportError::InvalidColor {
+                    key: key.clone(),
+                    value: value.to_string(),
+                })
+            }
+        }
+    }
+    builder.finish()
+}
+
+/// Reads the `colors` of an Alacritty TOML configuration.
+pub fn alacritty_toml(text: &str) -> Result<Palette, ImportError> {
+    alacritty(&toml_entries(text)?)
+}
+
+/// Reads the `colors` of an Alacritty YAML configuration.
+pub fn alacritty_yaml(text: &str) -> Result<Palette, ImportError> {
+    alacritty(&yaml_entries(text)?)
+}
+
+/// Reads the `foreground`, `background` and `colorN` entries of a kitty configuration.
+pub fn kitty(text: &str) -> Result<Palette, ImportError> {
+    let mut builder = Builder::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut words = line.splitn(2, char::is_whitespace);
+        if let (Some(key), Some(value)) = (words.next(), words.next()) {
+            if let Some(slot) = terminal_slot(key) {
+                builder.set(slot, key, value)?;
+            }
+        }
+    }
+    builder.finish()
+}
+
+/// Reads the `foreground`, `background` and `colorN` resources of any class, such as
+/// `*.color0` or `URxvt.background`. Values can name a `#define`.
+pub fn xresources(text: &str) -> Result<Palette, ImportError> {
+    let mut builder = Builder::new();
+    let mut defines: HashMap<&str, &str> = HashMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('!') {
+            continue;
+        }
+        if let Some(directive) = line.strip_prefix('#') {
+            let mut words = directive.split_whitespace();
+            if let (Some("define"), Some(name), Some(value)) =
+                (words.next(), words.next(), words.next())
+            {
+                defines.insert(name, value);
+            }
+            continue;
+        }
+        let (resource, value) = line.split_once(':').ok_or_else(|| ImportError::Syntax {
+            line: index + 1,
+            message: format!("expected 'resource: value', found '{}'", line),
+        })?;
+        let key = resource.trim().rsplit(['.', '*']).next().unwrap_or("");
+        if let Some(slot) = terminal_slot(key) {
+            let value = value.trim();
+            let value = defines.get(value).copied().unwrap_or(value);
+            builder.set(slot, resource.trim(), value)?;
+        }
+    }
+    builder.finish()
+}
+
+/// Reads a base16 or base24 YAML scheme, flat or with its colors under `palette`.
+pub fn base16(text: &str) -> Result<Palette, ImportError> {
+    let entries = yaml_entries(text)?;
+    let find = |slot: &str| {
+        entries.iter().find(|entry| {
+            let name = entry.path.rsplit('.').next().unwrap_or("");
+            name.eq_ignore_ascii_case(slot)
+        })
+    };
+    let color = |slot: &str| {
+        let entry = find(slot).ok_or_else(|| ImportError::MissingColor(slot.to_owned()))?;
+        parse_color(&entry.path, &entry.value)
+    };
+    let slots = if find("base10").is_some() {
+        BASE24_SLOTS
+    } else {
+        BASE16_SLOTS
+    };
+    let mut system = [RGB(0, 0, 0); 16];
+    for (rgb, slot) in system.iter_mut().zip(slots.iter()) {
+        *rgb = color(slot)?;
+    }
+    Ok(Palette::new(color("base05")?, color("base00")?, system))
+}
+
+/// A palette entry.
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Foreground,
+    Background,
+    Color(u8),
+}
+
+/// A palette filled from a scheme, starting from the xterm colors.
+struct Builder {
+    palette: Palette,
+    found: bool,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Builder {
+            palette: Palette::xterm(),
+            found: false,
+        }
+    }
+
+    fn set(&mut self, slot: Slot, key: &str, value: &str) -> Result<(), ImportError> {
+        let color = parse_color(key, value)?;
+        self.put(slot, color);
+        Ok(())
+    }
+
+    fn put(&mut self, slot: Slot, color: RGB) {
+        match slot {
+            Slot::Foreground => self.palette.foreground = color,
+            Slot::Background => self.palette.background = color,
+            Slot::Color(index) => self.palette.colors[index as usize] = color,
+        }
+        self.found = true;
+    }
+
+    fn finish(self) -> Result<Palette, ImportError> {
+        if self.found {
+            Ok(self.palette)
+        } else {
+            Err(ImportError::NoColors)
+        }
+    }
+}
+
+/// Parses a color value: any notation [`RGB`] parses, bare `rrggbb` digits or a color name.
+fn parse_color(key: &str, value: &str) -> Result<RGB, ImportError> {
+    let text = unquote(value);
+    let bare = text.len() == 6 && text.bytes().all(|byte| byte.is_ascii_hexdigit());
+    let color = if bare {
+        RGB::from_hex(&format!("#{}", text)).ok()
+    } else {
+        text.parse().ok().or_else(|| names::lookup(text))
+    };
+    color.ok_or_else(|| ImportError::InvalidColor {
+        key: key.to_owned(),
+        value: text.to_owned(),
+    })
+}
+
+/// Returns the index of an ANSI color name, `purple` standing for magenta.
+fn ansi_index(name: &str) -> Option<u8> {
+    if name == "purple" {
+        return Some(5);
+    }
+    SYSTEM_NAMES
+        .iter()
+        .position(|ansi| *ansi == name)
+        .map(|index| index as u8)
+}
+
+/// Returns the slot of a `foreground`, `background` or `colorN` key.
+fn terminal_slot(key: &str) -> Option<Slot> {
+    match key {
+        "foreground" => Some(Slot::Foreground),
+        "background" => Some(Slot::Background),
+        key => key.strip_prefix("color")?.parse().ok().map(Slot::Color),
+    }
+}
+
+fn iterm2_slot(name: &str) -> Option<Slot> {
+    match name {
+        "Foreground Color" => Some(Slot::Foreground),
+        "Background Color" => Some(Slot::Background),
+        name => {
+            let index: u8 = name
+                .strip_prefix("Ansi ")?
+                .strip_suffix(" Color")?
+                .parse()
+                .ok()?;
+            if index < 16 {
+                Some(Slot::Color(index))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Maps the `colors` entries of an Alacritty configuration to the palette.
+fn alacritty(entries: &[Entry]) -> Result<Palette, ImportError> {
+    let mut builder = Builder::new();
+    let mut indexed: BTreeMap<&str, (Option<&Entry>, Option<&Entry>)> = BTreeMap::new();
+    for entry in entries {
+        let segments: Vec<&str> = entry.path.split('.').collect();
+        let slot = match segments.as_slice() {
+            ["colors", "primary", "foreground"] => Slot::Foreground,
+            ["colors", "primary", "background"] => Slot::Background,
+            ["colors", "normal", name] => match ansi_index(name) {
+                Some(index) => Slot::Color(index),
+                None => continue,
+            },
+            ["colors", "bright", name] => match ansi_index(name) {
+                Some(index) => Slot::Color(index + 8),
+                None => continue,
+            },
+            ["colors", "indexed_colors", item, field] => {
+                let pair = indexed.entry(item).or_default();
+                match *field {
+                    "index" => pair.0 = Some(entry),
+                    "color" => pair.1 = Some(entry),
+                    _ => {}
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        builder.set(slot, &entry.path, &entry.value)?;
+    }
+    for pair in indexed.values() {
+        if let (Some(index), Some(color)) = pair {
+            let slot =
+                index
+                    .value
+                    .parse()
+                    .map(Slot::Color)
+                    .map_err(|_| ImportError::InvalidColor {
+                        key: index.path.clone(),
+                        value: index.value.clone(),
+                    })?;
+            builder.set(slot, &color.path, &color.value)?;
+        }
+    }
+    builder.finish()
+}
+
+/// A scalar of a TOML or YAML document, with the dotted path of its keys. The items
+/// of arrays are numbered from 0.
+#[derive(Debug)]
+struct Entry {
+    path: String,
+    value: String,
+}
+
+/// Flattens the scalars of a TOML document. Arrays of scalars are skipped.
+fn toml_entries(text: &str) -> Result<Vec<Entry>, ImportError> {
+    let mut entries = Vec::new();
+    let mut prefix = String::new();
+    let mut tables: HashMap<String, usize> = HashMap::new();
+    let mut depth = 0;
+    for (index, line) in text.lines().enumerate() {
+        let line = strip_comment(line, false).trim();
+        if depth > 0 {
+            depth = bracket_depth(line, depth);
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix("[[")
+            .and_then(|rest| rest.strip_suffix("]]"))
+        {
+            let name = dotted_key(name);
+            let count = tables.entry(name.clone()).or_insert(0);
+            prefix = format!("{}.{}", name, count);
+            *count += 1;
+        } else if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            prefix = dotted_key(name);
+        } else if let Some((key, value)) = split_pair(line, '=') {
+            let path = join(&prefix, &dotted_key(key));
+            let value = value.trim();
+            if let Some(table) = value
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+            {
+                for pair in split_outside_quotes(table, ',') {
+                    if let Some((key, value)) = split_pair(pair, '=') {
+                        entries.push(Entry {
+                            path: join(&path, &dotted_key(key)),
+                            value: unquote(value).to_owned(),
+                        });
+                    }
+                }
+            } else if value.starts_with('[') {
+                depth = bracket_depth(value, 0);
+            } else {
+                entries.push(Entry {
+                    path,
+                    value: unquote(value).to_owned(),
+                });
+            }
+        } else {
+            return Err(ImportError::Syntax {
+                line: index + 1,
+                message: format!("expected a table or 'key = value', found '{}'", line),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Flattens the scalars of a YAML document, in block style with flow style mappings
+/// as sequence items or values.
+fn yaml_entries(text: &str) -> Result<Vec<Entry>, ImportError> {
+    let mut entries = Vec::new();
+    // the indentation, key and whether it is a sequence item, of the open collections
+    let mut stack: Vec<(usize, String, bool)> = Vec::new();
+    let mut items: HashMap<String, usize> = HashMap::new();
+    let mut block: Option<usize> = None;
+    for (index, line) in text.lines().enumerate() {
+        let content = strip_comment(line, true).trim_end();
+        let trimmed = content.trim_start();
+        let mut indent = content.len() - trimmed.len();
+        if let Some(block_indent) = block {
+            if trimmed.is_empty() || indent > block_indent {
+                continue;
+            }
+            block = None;
+        }
+        if trimmed.is_empty() || trimmed == "---" || trimmed == "..." {
+            continue;
+        }
+        let mut rest = trimmed;
+        if rest == "-" || rest.starts_with("- ") {
+            while let Some((open, _, item)) = stack.last() {
+                if *open > indent || (*open == indent && *item) {
+                    stack.pop();
+                } else {
+                    break;
+                }
+            }
+            let parent = yaml_path(&stack);
+            let count = items.entry(parent).or_insert(0);
+            stack.push((indent, count.to_string(), true));
+            *count += 1;
+            rest = rest[1..].trim_start();
+            indent += trimmed.len() - rest.len();
+            if rest.is_empty() {
+                continue;
+            }
+            if rest.starts_with('{') || split_yaml_pair(rest).is_none() {
+                let path = yaml_path(&stack);
+                yaml_value(&mut entries, path, rest);
+                continue;
+            }
+        }
+        let (key, value) = split_yaml_pair(rest).ok_or_else(|| ImportError::Syntax {
+            line: index + 1,
+            message: format!("expected 'key: value', found '{}'", rest),
+        })?;
+        while let Some((open, _, _)) = stack.last() {
+            if *open >= indent {
+                stack.pop();
+            } else {
+                break;
+            }
+        }
+        let key = unquote(key).to_owned();
+        let value = value.trim();
+        if value.is_empty() {
+            stack.push((indent, key, false));
+        } else if value.starts_with('|') || value.starts_with('>') {
+            block = Some(indent);
+        } else {
+            let path = join(&yaml_path(&stack), &key);
+            yaml_value(&mut entries, path, value);
+        }
+    }
+    Ok(entries)
+}
+
+/// Adds a YAML value, flattening a flow style mapping.
+fn yaml_value(entries: &mut Vec<Entry>, path: String, value: &str) {
+    match value
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+    {
+        Some(mapping) => {
+            for pair in split_outside_quotes(mapping, ',') {
+                if let Some((key, value)) = split_yaml_pair(pair.trim()) {
+                    entries.push(Entry {
+                        path: join(&path, unquote(key)),
+                        value: unquote(value).to_owned(),
+                    });
+                }
+            }
+        }
+        None => entries.push(Entry {
+            path,
+            value: unquote(value).to_owned(),
+        }),
+    }
+}
+
+fn yaml_path(stack: &[(usize, String, bool)]) -> String {
+    let keys: Vec<&str> = stack.iter().map(|(_, key, _)| key.as_str()).collect();
+    keys.join(".")
+}
+
+/// Splits `key: value` at the first colon outside quotes followed by a space or the end.
+fn split_yaml_pair(text: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == ':' => {
+                let value = &text[index + 1..];
+                if value.is_empty() || value.starts_with(char::is_whitespace) {
+                    return Some((&text[..index], value));
+                }
+            }
+            None => {}
+        }
+    }
+    None
+}
+
+/// Splits `key = value` at the first separator outside quotes.
+fn split_pair(text: &str, separator: char) -> Option<(&str, &str)> {
+    let parts = split_outside_quotes(text, separator);
+    if parts.len() < 2 {
+        return None;
+    }
+    let key = parts[0];
+    Some((key, &text[key.len() + 1..]))
+}
+
+fn split_outside_quotes(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == separator => {
+                parts.push(&text[start..index]);
+                start = index + c.len_utf8();
+            }
+            None => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Removes a comment starting with `#` outside quotes. In YAML, the `#` must follow a
+/// space or start the line.
+fn strip_comment(line: &str, yaml: bool) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && (!yaml || previous.is_whitespace()) => return &line[..index],
+            None => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+/// Returns the depth of nested brackets at the end of the line.
+fn bracket_depth(line: &str, mut depth: usize) -> usize {
+    let mut quote = None;
+    for c in line.chars() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '[' => depth += 1,
+            None if c == ']' => depth = depth.saturating_sub(1),
+            None => {}
+        }
+    }
+    depth
+}
+
+/// Normalizes a dotted TOML key, removing spaces and quotes around its parts.
+fn dotted_key(key: &str) -> String {
+    let parts: Vec<&str> = split_outside_quotes(key, '.')
+        .into_iter()
+        .map(unquote)
+        .collect();
+    parts.join(".")
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn unquote(text: &str) -> &str {
+    let text = text.trim();
+    for quote in ['"', '\''] {
+        if text.len() >= 2 && text.starts_with(quote) && text.ends_with(quote) {
+            return &text[1..text.len() - 1];
+        }
+    }
+    text
+}
+
+/// A token of an XML document.
+enum Xml<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+/// Splits an XML document into tags and text, with their line numbers. Declarations,
+/// comments and attributes are skipped, and empty elements are opened and closed.
+fn xml_tokens(text: &str) -> Result<Vec<(usize, Xml<'_>)>, ImportError> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let line = line_at(text, text.len() - rest.len() + start);
+        let content = rest[..start].trim();
+        if !content.is_empty() {
+            tokens.push((line, Xml::Text(content)));
+        }
+        let tag = &rest[start..];
+        let (terminator, skip) = if tag.starts_with("<!--") {
+            ("-->", true)
+        } else if tag.starts_with("<?") || tag.starts_with("<!") {
+            (">", true)
+        } else {
+            (">", false)
+        };
+        let end = tag.find(terminator).ok_or_else(|| ImportError::Syntax {
+            line,
+            message: "unterminated tag".to_owned(),
+        })?;
+        rest = &tag[end + terminator.len()..];
+        if skip {
+            continue;
+        }
+        let inner = &tag[1..end];
+        let name = inner
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("");
+        if inner.starts_with('/') {
+            tokens.push((line, Xml::Close(name)));
+        } else {
+            tokens.push((line, Xml::Open(name)));
+            if inner.ends_with('/') {
+                tokens.push((line, Xml::Close(name)));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Returns the line number of a byte offset.
+fn line_at(text: &str, offset: usize) -> usize {
+    text[..offset].matches('\n').count() + 1
+}
+
+/// A JSON value.
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn member(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => value.fmt(f),
+            Json::Number(value) => value.fmt(f),
+            Json::String(value) => write!(f, "{:?}", value),
+            Json::Array(_) => write!(f, "[...]"),
+            Json::Object(_) => write!(f, "{{...}}"),
+        }
+    }
+}
+
+/// A JSON parser accepting the comments and trailing commas of Windows Terminal settings.
+struct JsonParser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(text: &'a str) -> Self {
+        JsonParser { text, position: 0 }
+    }
+
+    fn document(&mut self) -> Result<Json, ImportError> {
+        let value = self.value()?;
+        self.skip_space()?;
+        if self.position < self.text.len() {
+            return Err(self.error("unexpected characters after the document"));
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Json, ImportError> {
+        self.skip_space()?;
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Json::String),
+            Some(_) => self.literal(),
+            None => Err(self.error("unexpected end of the document")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ImportError> {
+        self.position += 1;
+        let mut members = Vec::new();
+        loop {
+            self.skip_space()?;
+            match self.peek() {
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(Json::Object(members));
+                }
+                Some('"') => {
+                    let key = self.string()?;
+                    self.skip_space()?;
+                    if self.peek() != Some(':') {
+                        return Err(self.error("expected ':'"));
+                    }
+                    self.position += 1;
+                    let value = self.value()?;
+                    members.push((key, value));
+                    self.separator('}')?;
+                }
+                _ => return Err(self.error("expected a key")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, ImportError> {
+        self.position += 1;
+        let mut values = Vec::new();
+        loop {
+            self.skip_space()?;
+            if self.peek() == Some(']') {
+                self.position += 1;
+                return Ok(Json::Array(values));
+            }
+            values.push(self.value()?);
+            self.separator(']')?;
+        }
+    }
+
+    fn separator(&mut self, close: char) -> Result<(), ImportError> {
+        self.skip_space()?;
+        match self.peek() {
+            Some(',') => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(c) if c == close => Ok(()),
+            _ => Err(self.error(&format!("expected ',' or '{}'", close))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ImportError> {
+        let text = self.text;
+        let mut value = String::new();
+        let mut chars = text[self.position + 1..].char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.position += offset + 2;
+                    return Ok(value);
+                }
+                '\\' => match chars.next().map(|(_, escape)| escape) {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some('b') => value.push('\u{0008}'),
+                    Some('f') => value.push('\u{000c}'),
+                    Some('u') => {
+                        let digits: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                        let code = u32::from_str_radix(&digits, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .unwrap_or(char::REPLACEMENT_CHARACTER);
+                        value.push(code);
+                    }
+                    Some(escape @ ('"' | '\\' | '/')) => value.push(escape),
+                    _ => return Err(self.error("invalid escape sequence")),
+                },
+                c => value.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn literal(&mut self) -> Result<Json, ImportError> {
+        let rest = &self.text[self.position..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)))
+            .unwrap_or(rest.len());
+        let value = match &rest[..len] {
+            "null" => Json::Null,
+            "true" => Json::Bool(true),
+            "false" => Json::Bool(false),
+            number => match number.parse() {
+                Ok(number) => Json::Number(number),
+                _ => return Err(self.error("unexpected character")),
+            },
+        };
+        self.position += len;
+        Ok(value)
+    }
+
+    fn skip_space(&mut self) -> Result<(), ImportError> {
+        loop {
+            let rest = &self.text[self.position..];
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if trimmed.starts_with("/*") {
+                let end = trimmed
+                    .find("*/")
+                    .ok_or_else(|| self.error("unterminated comment"))?;
+                self.position += end + 2;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn error(&self, message: &str) -> ImportError {
+        ImportError::Syntax {
+            line: line_at(self.text, self.position),
+            message: message.to_owned(),
+        }
+    }
+}
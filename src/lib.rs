@@ -11,15 +11,50 @@ followed immediately by a bracket: **ESC[**.
 */
 pub mod color;
 
+pub mod blend;
+pub mod capabilities;
+pub mod contrast;
+pub mod dircolors;
+pub mod export;
+pub mod gradient;
+pub mod html;
+pub mod hyperlink;
+pub mod import;
 pub mod macros;
+pub mod names;
+pub mod palette;
+pub mod parser;
 pub mod printer;
+pub mod query;
+pub mod scheme;
+pub mod space;
 pub mod style;
+pub mod svg;
+pub mod terminal;
+pub mod theme;
+pub mod vision;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::color::*;
+    use blend::*;
+    use contrast::*;
+    use gradient::*;
+    use html::*;
+    use palette::*;
+    use parser::*;
     use printer::*;
+    use query::*;
+    use space::*;
+    use style::*;
+    use svg::*;
+    use terminal::*;
+    use theme::*;
+    use vision::*;
 
     #[test]
     fn test_color16_printer() {
@@ -116,4 +151,1603 @@ mod tests {
     fn test_underline_macro() {
         underline!("this is an underline text");
     }
+
+    #[test]
+    fn test_success_macro() {
+        success!("this is a success text");
+    }
+
+    #[test]
+    fn test_muted_macro() {
+        muted!("this is a muted text");
+    }
+
+    #[test]
+    fn test_emphasis_macro() {
+        emphasis!("this is an emphasis text");
+    }
+
+    #[test]
+    fn test_code_macro() {
+        code!("this is a code text");
+    }
+
+    #[test]
+    fn test_link_macro() {
+        link!("this is a link text");
+    }
+
+    #[test]
+    fn test_parser_sgr() {
+        let tokens = parse("\u{001b}[1;38;5;208;48:2::10:20:30mhot\u{001b}[m");
+        let expected = vec![
+            Token::Sgr(vec![
+                Sgr::Bold,
+                Sgr::Foreground(Color::C256(208)),
+                Sgr::Background(Color::Rgb(RGB(10, 20, 30))),
+            ]),
+            Token::Text("hot".to_owned()),
+            Token::Sgr(vec![Sgr::Reset]),
+        ];
+        assert_eq!(expected, tokens);
+    }
+
+    #[test]
+    fn test_sgr_decode_empty_params() {
+        assert_eq!(vec![Sgr::Reset], Sgr::decode(&[vec![]]));
+        assert_eq!(vec![Sgr::Bold, Sgr::Reset], Sgr::decode(&[vec![1], vec![]]));
+        assert_eq!(
+            vec![Sgr::Foreground(Color::C256(0))],
+            Sgr::decode(&[vec![38], vec![5], vec![]])
+        );
+    }
+
+    #[test]
+    fn test_parser_sequences() {
+        let tokens =
+            parse("a\r\n\u{001b}[?25l\u{001b}]0;title\u{0007}\u{001b}]8;;x\u{001b}\\\u{001b}(B");
+        let expected = vec![
+            Token::Text("a".to_owned()),
+            Token::Control(b'\r'),
+            Token::Control(b'\n'),
+            Token::Csi {
+                params: vec![vec![25]],
+                intermediates: vec![b'?'],
+                action: 'l',
+            },
+            Token::Osc("0;title".to_owned()),
+            Token::Osc("8;;x".to_owned()),
+            Token::Esc {
+                intermediates: vec![b'('],
+                action: 'B',
+            },
+        ];
+        assert_eq!(expected, tokens);
+    }
+
+    #[test]
+    fn test_parser_malformed_input() {
+        let mut parser = Parser::new();
+        let mut tokens = parser.advance(b"\xe2\x94");
+        tokens.extend(parser.advance(b"\x80\xff\x1b[31"));
+        tokens.extend(parser.advance(b"\x1b[1mok\x1b[38;5"));
+        tokens.extend(parser.finish());
+        let expected = vec![
+            Token::Text("\u{2500}\u{fffd}".to_owned()),
+            Token::Sgr(vec![Sgr::Bold]),
+            Token::Text("ok".to_owned()),
+        ];
+        assert_eq!(expected, tokens);
+
+        let tokens = parse("\u{001b}[38;5;300m");
+        assert_eq!(
+            vec![Token::Sgr(vec![Sgr::Unknown(vec![38, 5, 300])])],
+            tokens
+        );
+    }
+
+    #[test]
+    fn test_style_builder_renders_literally() {
+        assert_eq!(
+            "\u{001b}[41;37mx\u{001b}[0m",
+            "x".print_c16(BG_RED, FG_WHITE)
+        );
+        assert_eq!(
+            "\u{001b}[31;32mx\u{001b}[0m",
+            "x".print_c16(FG_RED, FG_GREEN)
+        );
+        assert_eq!(
+            "\u{001b}[38;5;300;48;5;0mx\u{001b}[0m",
+            "x".print_c256(300, 0)
+        );
+
+        let style = StyleBuilder::new()
+            .csi()
+            .color(31)
+            .end_sgr()
+            .message()
+            .csi()
+            .color(1)
+            .end_sgr()
+            .build();
+        assert_eq!("\u{001b}[31mx\u{001b}[1m", style.render("x"));
+    }
+
+    #[test]
+    fn test_style_from_sgr() {
+        let style = TextStyle::from_sgr("01;4:3;38:2::255:136:0;49").unwrap();
+        assert!(style.bold);
+        assert!(style.underline);
+        assert_eq!(Some(Color::Rgb(RGB(255, 136, 0))), style.foreground);
+        assert_eq!(Some(Color::Default), style.background);
+        assert_eq!(style, TextStyle::from_sgr(&style.to_sgr()).unwrap());
+        assert_eq!(TextStyle::default(), TextStyle::from_sgr("").unwrap());
+        assert_eq!(TextStyle::default(), TextStyle::from_sgr("1;0").unwrap());
+    }
+
+    #[test]
+    fn test_style_from_sgr_errors() {
+        assert_eq!(Err(SgrError::UnknownCode(66)), TextStyle::from_sgr("1;66"));
+        assert_eq!(
+            Err(SgrError::OutOfRange(vec![48, 5, 256])),
+            TextStyle::from_sgr("48;5;256")
+        );
+        assert_eq!(
+            Err(SgrError::OutOfRange(vec![38, 2, 1])),
+            TextStyle::from_sgr("38;2;1")
+        );
+        assert_eq!(
+            Err(SgrError::InvalidNumber("red".to_owned())),
+            TextStyle::from_sgr("1;red")
+        );
+    }
+
+    #[test]
+    fn test_html_inline_styles() {
+        let input = format!(
+            "{} & {}\n{}",
+            "a".print_c256(208, 16),
+            "<b>".print_24bit(RGB(1, 2, 3), RGB(255, 255, 255)),
+            "\u{001b}[1;3;4;5;9mx\u{001b}[7my"
+        );
+        let expected = "<span style=\"color:#ff8700;background-color:#000000\">a</span> &amp; \
+            <span style=\"color:#010203;background-color:#ffffff\">&lt;b&gt;</span>\n\
+            <span style=\"font-weight:bold;font-style:italic;animation:ansi-blink 1s steps(1) infinite;\
+            text-decoration:underline line-through\">x</span>\
+            <span style=\"color:#000000;background-color:#e5e5e5;font-weight:bold;font-style:italic;\
+            animation:ansi-blink 1s steps(1) infinite;text-decoration:underline line-through\">y</span>";
+        assert_eq!(expected, HtmlConverter::new().convert(&input));
+    }
+
+    #[test]
+    fn test_html_classes() {
+        let converter = HtmlConverter::new().classes().class_prefix("t-");
+        let input = format!("{}{}", "a".print_c16(FG_LIGHT_RED, BG_DEFAULT), "b".blink());
+        let expected = "<span class=\"t-fg-9\">a</span><span class=\"t-fg-1 t-blink\">b</span>";
+        assert_eq!(expected, converter.convert(&input));
+
+        let stylesheet = converter.stylesheet();
+        assert!(stylesheet.contains(".t-fg-9 { color: #ff0000; }"));
+        assert!(stylesheet.contains(".t-bg-255 { background-color: #eeeeee; }"));
+    }
+
+    #[test]
+    fn test_html_blink_modes_agree() {
+        let inline = HtmlConverter::new().class_prefix("t-");
+        let classes = HtmlConverter::new().classes().class_prefix("t-");
+        let input = "\u{001b}[5mx";
+        assert_eq!(
+            "<span style=\"animation:t-blink 1s steps(1) infinite\">x</span>",
+            inline.convert(input)
+        );
+        assert_eq!("<span class=\"t-blink\">x</span>", classes.convert(input));
+
+        let stylesheet = inline.stylesheet();
+        assert_eq!(stylesheet, classes.stylesheet());
+        assert!(stylesheet.contains("@keyframes t-blink { 50% { opacity: 0; } }"));
+        assert!(stylesheet.contains(".t-blink { animation: t-blink 1s steps(1) infinite; }"));
+        assert!(!inline.convert(input).contains("text-decoration"));
+    }
+
+    #[test]
+    fn test_html_custom_palette() {
+        let mut palette = Palette::xterm();
+        palette.colors[1] = RGB(170, 0, 0);
+        palette.colors[196] = RGB(250, 0, 0);
+        assert_eq!(RGB(8, 8, 8), palette.colors[232]);
+        assert_eq!(RGB(95, 135, 175), palette.colors[67]);
+
+        let converter = HtmlConverter::new().palette(palette);
+        let input = format!("{}{}", "a".danger(), "b".print_c256(196, 1));
+        let expected = "<span style=\"color:#aa0000\">a</span>\
+            <span style=\"color:#fa0000;background-color:#aa0000\">b</span>";
+        assert_eq!(expected, converter.convert(&input));
+    }
+
+    #[test]
+    fn test_svg_renderer() {
+        let input = format!("ab\rA{}\n\tz", " <x> ".print_c16(FG_BLACK, BG_LIGHT_WHITE));
+        let svg = SvgRenderer::new()
+            .chrome(false)
+            .font_size(10.0)
+            .line_height(2.0)
+            .padding(0.0)
+            .render(&input);
+
+        let expected = "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"54\" height=\"40\" viewBox=\"0 0 54 40\">\n\
+            <style>text{font-family:Menlo, Consolas, &#39;DejaVu Sans Mono&#39;, monospace;font-size:10px;white-space:pre}</style>\n\
+            <rect width=\"54\" height=\"40\" rx=\"6\" fill=\"#000000\"/>\n\
+            <rect x=\"6\" y=\"0\" width=\"30\" height=\"20\" fill=\"#ffffff\"/>\n\
+            <text y=\"10\" dominant-baseline=\"middle\" xml:space=\"preserve\">\
+            <tspan x=\"0\" fill=\"#e5e5e5\">A</tspan>\
+            <tspan x=\"12\" fill=\"#000000\">&lt;x&gt; </tspan></text>\n\
+            <text y=\"30\" dominant-baseline=\"middle\" xml:space=\"preserve\">\
+            <tspan x=\"48\" fill=\"#e5e5e5\">z</tspan></text>\n\
+            </svg>\n";
+        assert_eq!(expected, svg);
+    }
+
+    #[test]
+    fn test_terminal_resolves_styles() {
+        let mut separate = Terminal::new(10, 1);
+        separate.write("abc\u{001b}[1m\u{001b}[31mX\u{001b}[22;39mY");
+        let mut combined = Terminal::new(10, 1);
+        combined.write("abc\u{001b}[31;1mX\u{001b}[0mY");
+
+        let expected = TextStyle {
+            foreground: Some(Color::C16(1)),
+            bold: true,
+            ..TextStyle::default()
+        };
+        assert_eq!(expected, separate.cell(3, 0).unwrap().style);
+        assert_eq!(expected, combined.cell(3, 0).unwrap().style);
+        assert_eq!(TextStyle::default(), combined.cell(4, 0).unwrap().style);
+        assert_eq!(separate.cell(4, 0).unwrap().character, 'Y');
+    }
+
+    #[test]
+    fn test_terminal_cursor_and_erase() {
+        let mut terminal = Terminal::new(6, 3);
+        terminal.write("hello world\r\nnext");
+        assert_eq!("hello\nworld\nnext", terminal.text());
+
+        terminal.write("\u{001b}[1;3H\u{001b}[K\u{001b}[2BZ\u{001b}[2D\u{001b}[41mY");
+        assert_eq!("he\nworld\nnYZt", terminal.text());
+        assert_eq!((2, 2), terminal.cursor());
+
+        terminal.write("\n");
+        assert_eq!("world\nnYZt", terminal.text());
+        terminal.write("\u{001b}[2J");
+        assert_eq!("", terminal.text());
+        assert_eq!(
+            Some(Color::C16(1)),
+            terminal.cell(0, 0).unwrap().style.background
+        );
+        assert_eq!(None, terminal.cell(6, 0));
+    }
+
+    #[test]
+    fn test_rgb_parsing() {
+        assert_eq!(Ok(RGB(0x11, 0xaa, 0xff)), "#1af".parse());
+        assert_eq!(Ok(RGB(0x12, 0xab, 0xef)), " #12ABef ".parse());
+        assert_eq!(Ok(RGB(0x12, 0xab, 0xef)), "#12abef80".parse());
+        assert_eq!(Ok(RGB(0x12, 0xab, 0xef)), "0x12abef".parse());
+        assert_eq!(Ok(RGB(1, 22, 255)), "RGB( 1,22 , 255)".parse());
+        assert_eq!(RGB::from_u32(0x12abef), RGB::new(0x12, 0xab, 0xef));
+        assert_eq!(0x12abef, RGB(0x12, 0xab, 0xef).to_u32());
+        assert_eq!("#0012ab", RGB(0, 0x12, 0xab).to_string());
+
+        assert_eq!(
+            Err(ParseColorError::InvalidHex("#12345".to_owned())),
+            "#12345".parse::<RGB>()
+        );
+        assert_eq!(
+            Err(ParseColorError::InvalidHex("0xfff".to_owned())),
+            "0xfff".parse::<RGB>()
+        );
+        assert_eq!(
+            Err(ParseColorError::InvalidComponent("256".to_owned())),
+            "rgb(1, 2, 256)".parse::<RGB>()
+        );
+        assert_eq!(
+            Err(ParseColorError::UnknownFormat("tomato".to_owned())),
+            "tomato".parse::<RGB>()
+        );
+    }
+
+    #[test]
+    fn test_named_colors() {
+        assert_eq!(Some(RGB(0x70, 0x80, 0x90)), names::lookup("SlateGray"));
+        assert_eq!(Some(RGB(0x66, 0x33, 0x99)), names::lookup("rebecca_purple"));
+        assert_eq!(Some(RGB(0xc6, 0xe2, 0xff)), names::lookup("SlateGray1"));
+        assert_eq!(Some(RGB(0x80, 0x80, 0x80)), names::lookup("gray"));
+        assert_eq!(Some(RGB(0xbe, 0xbe, 0xbe)), names::x11("Gray"));
+        assert_eq!(None, names::css("gray50"));
+        assert_eq!(None, names::lookup("not a color"));
+
+        assert_eq!(("aqua", RGB(0, 255, 255)), names::nearest(RGB(0, 255, 255)));
+        assert_eq!("black", names::nearest(RGB(1, 1, 1)).0);
+        assert_eq!(148, names::CSS_COLORS.len());
+        let all: Vec<_> = names::names().collect();
+        assert_eq!(1, all.iter().filter(|(name, _)| *name == "gray").count());
+        assert!(all
+            .iter()
+            .all(|(name, color)| names::lookup(name) == Some(*color)));
+    }
+
+    #[test]
+    fn test_hsl_hsv_round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(5) {
+                    let color = RGB(r, g, b);
+                    assert_eq!(color, RGB::from(Hsl::from(color)));
+                    assert_eq!(color, RGB::from(Hsv::from(color)));
+                    assert_eq!(color, RGB::from(Hsl::from(Hsv::from(color))));
+                    assert_eq!(color, RGB::from(Hsv::from(Hsl::from(color))));
+                }
+            }
+        }
+
+        let hsv = Hsv::from(RGB(0, 0, 128));
+        assert_eq!(240.0, hsv.hue);
+        assert_eq!(1.0, hsv.saturation);
+        assert_eq!(-120.0 + 360.0, Hsl::new(-120.0, 2.0, 0.5).hue);
+    }
+
+    #[test]
+    fn test_rgb_hsl_operations() {
+        let color = RGB(0x33, 0x66, 0x99);
+        assert_eq!(RGB(0x66, 0x99, 0xcc), color.lighten(0.2));
+        assert_eq!(RGB(0x00, 0x00, 0x00), color.darken(1.0));
+        assert_eq!(RGB(0x1f, 0x66, 0xad), color.saturate(0.2));
+        assert_eq!(RGB(0x66, 0x66, 0x66), color.desaturate(1.0));
+        assert_eq!(RGB(0x99, 0x33, 0x66), color.rotate_hue(120.0));
+        assert_eq!(color, color.rotate_hue(-360.0));
+    }
+
+    #[test]
+    fn test_perceptual_spaces() {
+        let close = |expected: f64, value: f64| (expected - value).abs() < 1e-4;
+
+        let white = Lab::from(RGB(255, 255, 255));
+        assert!(close(100.0, white.l) && close(0.0, white.a) && close(0.0, white.b));
+        let red = Oklab::from(RGB(255, 0, 0));
+        assert!(close(0.627_955, red.l) && close(0.224_863, red.a) && close(0.125_846, red.b));
+        let red = Lch::from(RGB(255, 0, 0));
+        assert!((red.l - 53.24).abs() < 0.01 && (red.c - 104.55).abs() < 0.01);
+        assert!((Oklch::from(RGB(0, 0, 255)).h - 264.05).abs() < 0.01);
+
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(5) {
+                    let color = RGB(r, g, b);
+                    assert_eq!(color, RGB::from(Xyz::from(color)));
+                    assert_eq!(color, RGB::from(Lch::from(color)));
+                    assert_eq!(color, RGB::from(Oklch::from(color)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_delta_e() {
+        let lab = |l, a, b| Lab { l, a, b };
+        let close = |expected: f64, value: f64| (expected - value).abs() < 1e-4;
+
+        assert!(close(
+            2.0425,
+            lab(50.0, 2.6772, -79.7751).delta_e_2000(&lab(50.0, 0.0, -82.7485))
+        ));
+        assert!(close(
+            2.8615,
+            lab(50.0, 3.1571, -77.2803).delta_e_2000(&lab(50.0, 0.0, -82.7485))
+        ));
+        assert!(close(
+            27.1492,
+            lab(50.0, 2.5, 0.0).delta_e_2000(&lab(73.0, 25.0, -18.0))
+        ));
+        assert!(close(
+            7.1792,
+            lab(50.0, 2.49, -0.001).delta_e_2000(&lab(50.0, -2.49, 0.0009))
+        ));
+        assert!(close(
+            4.3065,
+            lab(50.0, 2.5, 0.0).delta_e_2000(&lab(50.0, 0.0, -2.5))
+        ));
+        assert!(close(
+            5.0,
+            lab(50.0, 0.0, 0.0).delta_e_76(&lab(53.0, 4.0, 0.0))
+        ));
+        assert!(close(
+            1.3950,
+            lab(50.0, 2.6772, -79.7751).delta_e_94(&lab(50.0, 0.0, -82.7485))
+        ));
+        let white = Oklab::from(RGB(255, 255, 255));
+        let black = Oklab::from(RGB(0, 0, 0));
+        assert!(close(1.0, white.distance(&black)));
+    }
+
+    #[test]
+    fn test_gradient_stops_and_spaces() {
+        let gradient = Gradient::with_stops(&[
+            (1.0, RGB(255, 255, 255)),
+            (0.25, RGB(0, 0, 0)),
+            (0.75, RGB(255, 0, 0)),
+        ]);
+        assert_eq!(RGB(0, 0, 0), gradient.at(0.0));
+        assert_eq!(RGB(128, 0, 0), gradient.at(0.5));
+        assert_eq!(RGB(255, 128, 128), gradient.at(0.875));
+        assert_eq!(RGB(255, 255, 255), gradient.at(2.0));
+
+        let black_white = Gradient::new(&[RGB(0, 0, 0), RGB(255, 255, 255)]);
+        assert_eq!(RGB(128, 128, 128), black_white.at(0.5));
+        let linear = black_white.clone().interpolation(Interpolation::LinearRgb);
+        assert_eq!(RGB(188, 188, 188), linear.at(0.5));
+        let oklab = black_white.interpolation(Interpolation::Oklab);
+        assert_eq!(RGB(99, 99, 99), oklab.at(0.5));
+
+        let hsl =
+            Gradient::new(&[RGB(255, 0, 0), RGB(255, 0, 255)]).interpolation(Interpolation::Hsl);
+        assert_eq!(RGB(255, 0, 128), hsl.at(0.5));
+        let gray =
+            Gradient::new(&[RGB(128, 128, 128), RGB(0, 0, 255)]).interpolation(Interpolation::Hsl);
+        assert_eq!(RGB(64, 64, 191), gray.at(0.5));
+
+        assert_eq!(
+            vec![RGB(10, 20, 30)],
+            Gradient::new(&[RGB(10, 20, 30)]).sample(1)
+        );
+        assert_eq!(4, gradient.sample(4).len());
+    }
+
+    #[test]
+    fn test_gradient_paint() {
+        let gradient = Gradient::new(&[RGB(255, 0, 0), RGB(0, 0, 255)]);
+        let painted = gradient.paint("abc");
+        let mut terminal = Terminal::new(3, 1);
+        terminal.write(&painted);
+        assert_eq!("abc", terminal.text());
+        assert_eq!(
+            Some(Color::Rgb(RGB(128, 0, 128))),
+            terminal.cell(1, 0).unwrap().style.foreground
+        );
+
+        let flat = Gradient::new(&[RGB(1, 2, 3)]).paint_background("ab");
+        assert_eq!("\u{001b}[48;2;1;2;3mab\u{001b}[0m", flat);
+        assert_eq!("", gradient.paint(""));
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let (black, white) = (RGB(0, 0, 0), RGB(255, 255, 255));
+        assert_eq!(21.0, contrast_ratio(black, white));
+        assert_eq!(21.0, contrast_ratio(white, black));
+        assert_eq!(1.0, contrast_ratio(white, white));
+        assert!((contrast_ratio(RGB(0x77, 0x77, 0x77), white) - 4.478).abs() < 0.001);
+
+        assert!((apca_contrast(black, white) - 106.04).abs() < 0.01);
+        assert!((apca_contrast(white, black) + 107.88).abs() < 0.01);
+        assert!((apca_contrast(RGB(0x88, 0x88, 0x88), white) - 63.06).abs() < 0.01);
+        assert!((apca_contrast(white, RGB(0x88, 0x88, 0x88)) + 68.54).abs() < 0.01);
+        assert_eq!(0.0, apca_contrast(white, white));
+
+        let palette = Palette::default();
+        let yellow_on_white = TextStyle::from_sgr("93;107").unwrap();
+        assert!(style_contrast_ratio(&yellow_on_white, &palette) < AA_LARGE);
+        let reversed = TextStyle::from_sgr("7").unwrap();
+        assert_eq!(
+            style_contrast_ratio(&TextStyle::default(), &palette),
+            style_contrast_ratio(&reversed, &palette)
+        );
+        assert!(style_apca_contrast(&TextStyle::default(), &palette) < 0.0);
+    }
+
+    #[test]
+    fn test_contrast_adjust() {
+        let white = RGB(255, 255, 255);
+        let candidates = [RGB(255, 255, 0), RGB(0, 0, 238), RGB(0, 0, 0)];
+        assert_eq!(
+            Some(RGB(0, 0, 238)),
+            pick_foreground(&candidates, white, AA_NORMAL)
+        );
+        assert_eq!(
+            Some(RGB(0, 0, 0)),
+            pick_foreground(&candidates, white, 30.0)
+        );
+        assert_eq!(None, pick_foreground(&[], white, AA_NORMAL));
+
+        let yellow = RGB(255, 255, 0);
+        let adjusted = adjust_foreground(yellow, white, AAA_NORMAL);
+        assert!(contrast_ratio(adjusted, white) >= AAA_NORMAL);
+        assert!(contrast_ratio(adjusted, white) < AAA_NORMAL + 0.2);
+        let hsl = Hsl::from(adjusted);
+        assert!((hsl.hue - 60.0).abs() < 10.0);
+
+        let navy = RGB(0, 0, 128);
+        assert!(
+            contrast_ratio(
+                adjust_foreground(navy, RGB(0, 0, 0), AA_NORMAL),
+                RGB(0, 0, 0)
+            ) >= AA_NORMAL
+        );
+        assert_eq!(white, adjust_foreground(white, RGB(0, 0, 0), AA_NORMAL));
+        assert_eq!(
+            RGB(0, 0, 0),
+            adjust_foreground(yellow, RGB(128, 128, 128), 21.0)
+        );
+    }
+
+    #[test]
+    fn test_vision_simulate() {
+        let red = RGB(255, 0, 0);
+        assert_eq!(RGB(0x6d, 0x5f, 0x00), red.simulate(Deficiency::Protanopia));
+        assert_eq!(
+            RGB(0xa3, 0x90, 0x00),
+            red.simulate(Deficiency::Deuteranopia)
+        );
+        assert_eq!(
+            RGB(0x00, 0xf7, 0xd9),
+            RGB(0, 255, 0).simulate(Deficiency::Tritanopia)
+        );
+        assert_eq!(red, red.simulate(Deficiency::Deuteranomaly(0.0)));
+        assert_eq!(
+            red.simulate(Deficiency::Protanopia),
+            red.simulate(Deficiency::Protanomaly(1.5))
+        );
+        assert_eq!(
+            RGB(0xc8, 0x44, 0x00),
+            red.simulate(Deficiency::Protanomaly(0.5))
+        );
+        for deficiency in DICHROMACIES.iter() {
+            let gray = RGB(128, 128, 128);
+            assert_eq!(gray, gray.simulate(*deficiency));
+        }
+    }
+
+    #[test]
+    fn test_vision_conflicts() {
+        let palette = Palette::default();
+        let style = |sgr: &str| TextStyle::from_sgr(sgr).unwrap();
+        let styles = [
+            ("danger", style("31")),
+            ("info", style("32")),
+            ("warn", style("33")),
+            ("primary", style("34")),
+            ("error", style("37;41")),
+            ("emphasis", style("1;33")),
+        ];
+        let conflicts = conflicts(&styles, &palette, THRESHOLD);
+        let pairs: Vec<(&str, &str, Deficiency)> = conflicts
+            .iter()
+            .map(|conflict| {
+                (
+                    conflict.first.as_str(),
+                    conflict.second.as_str(),
+                    conflict.deficiency,
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("info", "warn", Deficiency::Protanopia),
+                ("info", "warn", Deficiency::Deuteranopia),
+            ],
+            pairs
+        );
+        assert!(conflicts[0].distance < THRESHOLD);
+        assert!(vision::conflicts(&styles, &palette, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_blend_modes() {
+        let backdrop = RGB(51, 102, 204);
+        let source = Rgba::from(RGB(153, 153, 153));
+        let blend = |mode: BlendMode| source.blend(backdrop, mode);
+        assert_eq!(RGB(153, 153, 153), blend(BlendMode::Normal));
+        assert_eq!(RGB(31, 61, 122), blend(BlendMode::Multiply));
+        assert_eq!(RGB(173, 194, 235), blend(BlendMode::Screen));
+        assert_eq!(RGB(61, 122, 214), blend(BlendMode::Overlay));
+        assert_eq!(RGB(64, 114, 209), blend(BlendMode::SoftLight));
+        assert_eq!(RGB(102, 51, 51), blend(BlendMode::Difference));
+
+        let translucent = Rgba::new(RGB(153, 153, 153), 0.0);
+        assert_eq!(backdrop, translucent.blend(backdrop, BlendMode::Difference));
+        assert_eq!(
+            RGB(102, 128, 178),
+            Rgba::new(RGB(153, 153, 153), 0.5).over(backdrop)
+        );
+    }
+
+    #[test]
+    fn test_rgba() {
+        let color: Rgba = "#ff000080".parse().unwrap();
+        assert_eq!(Rgba(255, 0, 0, 128), color);
+        assert_eq!(Rgba(255, 0, 0, 136), "#f008".parse().unwrap());
+        assert_eq!(Rgba(255, 136, 0, 255), "rgb(255, 136, 0)".parse().unwrap());
+        assert_eq!(
+            Err(ParseColorError::InvalidHex("#ff00zz80".to_owned())),
+            "#ff00zz80".parse::<Rgba>()
+        );
+        assert_eq!("#ff000080", color.to_string());
+        assert_eq!(RGB(255, 0, 0), color.rgb());
+
+        let blue = Rgba(0, 0, 255, 128);
+        assert_eq!(Rgba(170, 0, 85, 192), color.over_rgba(blue));
+        assert_eq!(blue, Rgba(9, 9, 9, 0).over_rgba(blue));
+        assert_eq!(
+            Rgba(0, 0, 0, 0),
+            Rgba(9, 9, 9, 0).over_rgba(Rgba(1, 1, 1, 0))
+        );
+        assert_eq!(
+            color.over_rgba(blue).over(RGB(0, 0, 0)),
+            color.over(blue.over(RGB(0, 0, 0)))
+        );
+    }
+
+    #[test]
+    fn test_scheme_harmonies() {
+        let base = RGB(51, 102, 204);
+        assert_eq!([base, RGB(204, 153, 51)], scheme::complementary(base));
+        assert_eq!(
+            [base, RGB(51, 179, 204), RGB(77, 51, 204)],
+            scheme::analogous(base)
+        );
+        assert_eq!(
+            [base, RGB(204, 51, 102), RGB(102, 204, 51)],
+            scheme::triadic(base)
+        );
+        assert_eq!(
+            [
+                base,
+                RGB(153, 51, 204),
+                RGB(204, 153, 51),
+                RGB(102, 204, 51)
+            ],
+            scheme::tetradic(base)
+        );
+        assert_eq!(
+            [base, RGB(204, 76, 51), RGB(179, 204, 51)],
+            scheme::split_complementary(base)
+        );
+    }
+
+    #[test]
+    fn test_scheme_scales() {
+        let base = RGB(200, 100, 0);
+        assert_eq!(
+            vec![
+                base,
+                RGB(214, 139, 64),
+                RGB(227, 178, 128),
+                RGB(241, 216, 191)
+            ],
+            scheme::tints(base, 4)
+        );
+        assert_eq!(vec![base, RGB(100, 50, 0)], scheme::shades(base, 2));
+        assert_eq!(vec![base, RGB(164, 114, 64)], scheme::tones(base, 2));
+        assert!(scheme::tints(base, 0).is_empty());
+
+        let colors = scheme::categorical(base, 8);
+        assert_eq!(8, colors.len());
+        assert_eq!(base, colors[0]);
+        for (index, color) in colors.iter().enumerate() {
+            let lab = Lab::from(*color);
+            for other in colors.iter().skip(index + 1) {
+                assert!(lab.delta_e_2000(&Lab::from(*other)) > 15.0);
+            }
+        }
+        assert_eq!(colors, scheme::categorical(base, 8));
+        assert!(scheme::categorical(base, 0).is_empty());
+
+        let all = scheme::categorical(base, 5000);
+        assert!(all.len() < 5000);
+        assert_eq!(all[..8], colors[..]);
+        let distinct: std::collections::HashSet<RGB> = all.iter().copied().collect();
+        assert_eq!(all.len(), distinct.len());
+        assert_eq!(all, scheme::categorical(base, all.len() + 1));
+    }
+
+    #[test]
+    fn test_palette_presets() {
+        for name in PRESETS.iter() {
+            let palette = Palette::preset(name).unwrap();
+            assert_ne!(palette.foreground, palette.background);
+            assert_eq!(RGB(255, 135, 0), palette.colors[208]);
+        }
+        assert_eq!(None, Palette::preset("unknown"));
+        assert_eq!(Palette::xterm(), Palette::preset("xterm").unwrap());
+
+        let red = TextStyle::from_sgr("31").unwrap();
+        assert_eq!(RGB(197, 15, 31), Palette::windows10().resolve_style(&red).0);
+        assert_eq!(
+            RGB(220, 50, 47),
+            Palette::solarized_dark().resolve_style(&red).0
+        );
+        assert_eq!(
+            Palette::solarized_dark().colors,
+            Palette::solarized_light().colors
+        );
+        assert!(
+            style_contrast_ratio(&TextStyle::default(), &Palette::macos())
+                > style_contrast_ratio(&TextStyle::default(), &Palette::tango())
+        );
+    }
+
+    #[test]
+    fn test_palette_quantize() {
+        let xterm = Palette::xterm();
+        assert_eq!(Color::C16(9), xterm.quantize_16(RGB(250, 10, 10)));
+        assert_eq!(Color::C16(1), xterm.quantize_16(RGB(200, 10, 10)));
+        assert_eq!(Color::C256(196), xterm.quantize_256(RGB(255, 0, 1)));
+        assert_eq!(Color::C256(16), xterm.quantize_256(RGB(0, 0, 0)));
+        assert_eq!(Color::C256(244), xterm.quantize_256(RGB(130, 130, 130)));
+
+        let orange = RGB(203, 75, 22);
+        assert_eq!(Color::C16(9), Palette::solarized_dark().quantize_16(orange));
+        assert_eq!(Color::C16(1), xterm.quantize_16(orange));
+        assert_eq!(Color::C256(166), xterm.quantize_256(orange));
+        assert_eq!(
+            xterm.quantize_256(orange),
+            Palette::solarized_dark().quantize_256(orange)
+        );
+
+        let mut custom = Palette::xterm();
+        custom.colors[100] = RGB(1, 2, 3);
+        assert_eq!(Color::C256(100), custom.quantize_256(RGB(1, 2, 3)));
+    }
+
+    /// A terminal replaying scripted chunks of replies, then timing out.
+    struct FakeTerminal {
+        chunks: Vec<Vec<u8>>,
+    }
+
+    impl ReadTimeout for FakeTerminal {
+        fn read_timeout(
+            &mut self,
+            buffer: &mut [u8],
+            _timeout: std::time::Duration,
+        ) -> std::io::Result<usize> {
+            if self.chunks.is_empty() {
+                return Ok(0);
+            }
+            let chunk = self.chunks.remove(0);
+            buffer[..chunk.len()].copy_from_slice(&chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn test_query_colors() {
+        let terminal = FakeTerminal {
+            chunks: vec![
+                b"\x1b]10;rgb:d3d3/d7d7/cfcf\x07\x1b[?62;22c".to_vec(),
+                b"\x1b]11;rgb:2e".to_vec(),
+                b"2e/3434/3636\x1b\\\x1b[?62;22c".to_vec(),
+                b"\x1b]4;1;rgb:cc/00/00\x1b\\\x1b[?62;22c".to_vec(),
+                b"\x1b]4;2;rgb:4e/9a/06\x1b\\\x1b[?62;22c".to_vec(),
+            ],
+        };
+        let mut sent = Vec::new();
+        let mut query = Query::new(terminal, &mut sent);
+        assert_eq!(RGB(211, 215, 207), query.foreground().unwrap());
+        assert_eq!(RGB(46, 52, 54), query.background().unwrap());
+        assert_eq!(RGB(204, 0, 0), query.color(1).unwrap());
+        assert_eq!(RGB(78, 154, 6), query.color(2).unwrap());
+        assert_eq!(
+            "\u{1b}]10;?\u{7}\u{1b}[c\u{1b}]11;?\u{7}\u{1b}[c\u{1b}]4;1;?\u{7}\u{1b}[c\u{1b}]4;2;?\u{7}\u{1b}[c",
+            String::from_utf8(sent).unwrap()
+        );
+
+        let mut replies = String::from("\u{1b}]10;rgb:ffff/ffff/ffff\u{7}\u{1b}]11;rgb:0/0/0\u{7}");
+        for (index, color) in Palette::tango().colors[..16].iter().enumerate() {
+            let RGB(r, g, b) = color;
+            replies.push_str(&format!(
+                "\u{1b}]4;{};rgb:{:02x}/{:02x}/{:02x}\u{7}",
+                index, r, g, b
+            ));
+        }
+        replies.push_str("\u{1b}[?1;2c");
+        let palette = Query::new(replies.as_bytes(), std::io::sink())
+            .palette()
+            .unwrap();
+        assert_eq!(RGB(255, 255, 255), palette.foreground);
+        assert_eq!(RGB(0, 0, 0), palette.background);
+        assert_eq!(Palette::tango().colors, palette.colors);
+    }
+
+    #[test]
+    fn test_query_errors() {
+        let replies: &[u8] = b"\x1b[?62;22c";
+        let result = Query::new(replies, std::io::sink()).background();
+        assert!(matches!(result, Err(QueryError::Unsupported)));
+
+        let replies: &[u8] = b"";
+        let result = Query::new(replies, std::io::sink()).background();
+        assert!(matches!(result, Err(QueryError::Timeout)));
+        let replies: &[u8] = b"\x1b]11;rgb:ffff/ffff/ffff\x07";
+        let result = Query::new(replies, std::io::sink())
+            .timeout(std::time::Duration::from_secs(0))
+            .background();
+        assert!(matches!(result, Err(QueryError::Timeout)));
+
+        let replies: &[u8] = b"\x1b]11;rgb:zz/00/00\x07\x1b[?62c";
+        match Query::new(replies, std::io::sink()).background() {
+            Err(QueryError::InvalidReply(reply)) => assert_eq!("11;rgb:zz/00/00", reply),
+            _ => panic!("expected an invalid reply"),
+        }
+
+        assert_eq!(Ok(RGB(255, 136, 0)), "rgb:f/8/0".parse());
+        assert_eq!(Ok(RGB(255, 128, 0)), "rgb:fff/808/000".parse());
+        assert_eq!(Ok(RGB(1, 2, 3)), "rgba:0101/0202/0303/ffff".parse());
+        assert!("rgb:fffff/0/0".parse::<RGB>().is_err());
+        assert!("rgb:ff/00".parse::<RGB>().is_err());
+    }
+
+    #[test]
+    fn test_theme_variants() {
+        let dark = Theme::dark();
+        assert_eq!("message".error(), dark.paint(Role::Error, "message"));
+        assert_eq!("message".info(), dark.paint(Role::Info, "message"));
+        assert_eq!("message".blink(), dark.paint(Role::Blink, "message"));
+        assert_eq!(
+            "message".underline(),
+            dark.paint(Role::Underline, "message")
+        );
+        assert_eq!(Theme::default(), dark);
+
+        let light = Theme::light();
+        let palette = Palette::macos();
+        for role in [Role::Info, Role::Primary, Role::Warn, Role::Danger].iter() {
+            assert!(style_contrast_ratio(&light.style(*role), &palette) >= AA_NORMAL);
+        }
+        assert!(style_contrast_ratio(&dark.style(Role::Warn), &palette) < AA_LARGE);
+        assert_eq!("message", Theme::new().paint(Role::Warn, "message"));
+    }
+
+    #[test]
+    fn test_theme_selection() {
+        assert_eq!(
+            Appearance::Dark,
+            Appearance::from_background(RGB(0, 43, 54))
+        );
+        assert_eq!(
+            Appearance::Light,
+            Appearance::from_background(RGB(253, 246, 227))
+        );
+        assert_eq!(
+            Appearance::Dark,
+            Appearance::from_background(RGB(100, 100, 100))
+        );
+        assert_eq!(Some(Appearance::Light), Appearance::from_colorfgbg("0;15"));
+        assert_eq!(
+            Some(Appearance::Light),
+            Appearance::from_colorfgbg("0;default;7")
+        );
+        assert_eq!(Some(Appearance::Dark), Appearance::from_colorfgbg("15;8"));
+        assert_eq!(None, Appearance::from_colorfgbg("default;default"));
+        assert_eq!(None, Appearance::from_colorfgbg("15;16"));
+
+        let themes = AdaptiveTheme::default();
+        assert_eq!(&Theme::dark(), themes.select_for(None));
+        assert_eq!(&Theme::light(), themes.select_for(Some(Appearance::Light)));
+        let themes = themes.fallback(Appearance::Light);
+        assert_eq!(&Theme::light(), themes.select_for(None));
+        assert_eq!(&Theme::dark(), themes.select_for(Some(Appearance::Dark)));
+    }
+
+    #[test]
+    fn test_theme_roles() {
+        assert_eq!("\u{001b}[32;49mok\u{001b}[0m", "ok".info());
+        assert_eq!("\u{001b}[32;1mok\u{001b}[0m", "ok".success());
+        assert_eq!("\u{001b}[90mok\u{001b}[0m", "ok".muted());
+        assert_eq!("\u{001b}[1mok\u{001b}[0m", "ok".emphasis());
+        assert_eq!("\u{001b}[36mok\u{001b}[0m", "ok".code());
+        assert_eq!("\u{001b}[34;4mok\u{001b}[0m", "ok".link());
+        assert_eq!("ok".warn(), "ok".role(Role::Warn));
+
+        for role in Role::ALL.iter() {
+            assert_eq!(Some(*role), Role::from_name(role.name()));
+        }
+        assert_eq!(None, Role::from_name("unknown"));
+
+        let styles = Theme::dark().styles();
+        assert_eq!(Role::ALL.len(), styles.len());
+        assert_eq!(("info", Theme::dark().style(Role::Info)), styles[0]);
+        assert_eq!(
+            vec![("warn", TextStyle::from_sgr("33").unwrap())],
+            Theme::new()
+                .set(Role::Warn, TextStyle::from_sgr("33").unwrap())
+                .styles()
+        );
+        let light = Theme::light();
+        let palette = Palette::macos();
+        for role in [Role::Muted, Role::Code, Role::Success].iter() {
+            assert!(style_contrast_ratio(&light.style(*role), &palette) >= AA_NORMAL);
+        }
+    }
+
+    /// A printer implementing only the functions of the first releases.
+    struct Label(&'static str);
+
+    impl AsRef<str> for Label {
+        fn as_ref(&self) -> &str {
+            self.0
+        }
+    }
+
+    impl ColorPrinter for Label {
+        fn print_c16(&self, foreground: usize, background: usize) -> String {
+            self.0.print_c16(foreground, background)
+        }
+        fn print_c256(&self, foreground: usize, background: usize) -> String {
+            self.0.print_c256(foreground, background)
+        }
+        fn print_24bit(&self, foreground: RGB, background: RGB) -> String {
+            self.0.print_24bit(foreground, background)
+        }
+        fn warn(&self) -> String {
+            self.0.warn()
+        }
+        fn error(&self) -> String {
+            self.0.error()
+        }
+        fn danger(&self) -> String {
+            self.0.danger()
+        }
+        fn info(&self) -> String {
+            self.0.info()
+        }
+        fn primary(&self) -> String {
+            self.0.primary()
+        }
+        fn blink(&self) -> String {
+            self.0.blink()
+        }
+        fn underline(&self) -> String {
+            self.0.underline()
+        }
+    }
+
+    #[test]
+    fn test_printer_default_functions() {
+        let label = Label("ok");
+        assert_eq!("ok".success(), label.success());
+        assert_eq!("ok".link(), label.link());
+        assert_eq!("ok".role(Role::Muted), label.role(Role::Muted));
+        assert_eq!(
+            hyperlink::Hyperlink::new("https://example.com").render("ok"),
+            label.hyperlink("https://example.com")
+        );
+    }
+
+    #[test]
+    fn test_theme_scopes() {
+        let magenta = TextStyle::from_sgr("35").unwrap();
+        let cyan = TextStyle::from_sgr("36").unwrap();
+        let outer = theme::scope(Theme::new().set(Role::Warn, magenta));
+        assert_eq!("\u{001b}[35mw\u{001b}[0m", "w".warn());
+        assert_eq!("w", "w".info());
+        let inner = theme::scope(Theme::new().set(Role::Warn, cyan));
+        assert_eq!("\u{001b}[36mw\u{001b}[0m", "w".warn());
+
+        let other = std::thread::spawn(|| "w".warn()).join().unwrap();
+        assert_eq!("\u{001b}[33;49mw\u{001b}[0m", other);
+
+        drop(outer);
+        assert_eq!("\u{001b}[36mw\u{001b}[0m", "w".warn());
+        drop(inner);
+        assert_eq!("\u{001b}[33;49mw\u{001b}[0m", "w".warn());
+    }
+
+    #[test]
+    fn test_theme_custom_roles() {
+        let audit = Role::register("audit");
+        assert_eq!(Role::Custom("audit"), audit);
+        assert_eq!(audit, Role::register("audit"));
+        assert_eq!(Some(audit), Role::from_name("audit"));
+        assert_eq!(Role::Warn, Role::register("warn"));
+        assert!(Role::registered().contains(&audit));
+        assert_eq!("audit", audit.name());
+
+        let style = TextStyle::from_sgr("1;35").unwrap();
+        let guard = theme::scope(Theme::dark().set(audit, style));
+        assert_eq!("\u{001b}[35;1mentry\u{001b}[0m", "entry".role(audit));
+        drop(guard);
+        assert_eq!("entry", "entry".role(audit));
+
+        let theme = Theme::new()
+            .set(Role::Custom("zebra"), style)
+            .set(audit, style)
+            .set(Role::Info, style);
+        let names: Vec<&str> = theme.styles().iter().map(|(name, _)| *name).collect();
+        assert_eq!(vec!["info", "audit", "zebra"], names);
+    }
+
+    #[test]
+    fn test_color_and_style_strings() {
+        assert_eq!(Ok(Color::C16(9)), "Bright_Red".parse());
+        assert_eq!(Ok(Color::C256(208)), "208".parse());
+        assert_eq!(Ok(Color::Rgb(RGB(255, 99, 71))), "tomato".parse());
+        assert_eq!(Ok(Color::Default), "default".parse::<Color>());
+        assert!("256".parse::<Color>().is_err());
+        assert_eq!(Ok(Color::C16(9)), "bright red".parse());
+        for text in ["-1", "+1", "1-2", "2 5 5", "1_0"] {
+            assert_eq!(
+                Err(ParseColorError::InvalidComponent(text.to_owned())),
+                text.parse::<Color>()
+            );
+        }
+        assert_eq!("bright-red", Color::C16(9).to_string());
+        assert_eq!("208", Color::C256(208).to_string());
+
+        let style: TextStyle = "dim italic red on 236".parse().unwrap();
+        assert_eq!(Some(Color::C16(1)), style.foreground);
+        assert_eq!(Some(Color::C256(236)), style.background);
+        assert!(style.faint && style.italic && !style.bold);
+        assert_eq!("faint italic red on 236", style.to_string());
+        assert_eq!(Ok(style), style.to_string().parse());
+
+        assert_eq!(
+            Err(ParseStyleError::UnknownWord("shiny".to_owned())),
+            "bold shiny".parse::<TextStyle>()
+        );
+        assert_eq!(
+            Err(ParseStyleError::UnexpectedColor("blue".to_owned())),
+            "red blue".parse::<TextStyle>()
+        );
+        assert_eq!(
+            Err(ParseStyleError::MissingBackground),
+            "red on".parse::<TextStyle>()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_styles() {
+        let style: TextStyle = serde_json::from_str("\"bold #ff8800 on grey23\"").unwrap();
+        assert_eq!(Some(Color::Rgb(RGB(255, 136, 0))), style.foreground);
+        assert_eq!(
+            "\"bold #ff8800 on #3b3b3b\"",
+            serde_json::to_string(&style).unwrap()
+        );
+
+        let table: TextStyle =
+            serde_json::from_str(r#"{"fg": 208, "bg": "navy", "underline": true}"#).unwrap();
+        assert_eq!(Some(Color::C256(208)), table.foreground);
+        assert_eq!(Some(Color::Rgb(RGB(0, 0, 128))), table.background);
+        assert!(table.underline);
+
+        let rgb: RGB = serde_json::from_str("[1, 2, 3]").unwrap();
+        assert_eq!(RGB(1, 2, 3), rgb);
+        assert_eq!("\"#010203\"", serde_json::to_string(&rgb).unwrap());
+
+        let error = serde_json::from_str::<TextStyle>("\"bold shiny\"").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("invalid style 'bold shiny': unknown attribute or color 'shiny'"));
+        let error = serde_json::from_str::<TextStyle>(r#"{"weight": 1}"#).unwrap_err();
+        assert!(error.to_string().starts_with("unknown field `weight`"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_themes() {
+        let theme: Theme = serde_json::from_str(
+            r#"{"warn": "bold yellow", "error": {"fg": "white", "bg": "red"}}"#,
+        )
+        .unwrap();
+        assert_eq!("\u{001b}[33;1mw\u{001b}[0m", theme.paint(Role::Warn, "w"));
+        assert_eq!("\u{001b}[37;41me\u{001b}[0m", theme.paint(Role::Error, "e"));
+
+        let json = serde_json::to_string(&Theme::dark()).unwrap();
+        assert_eq!(Theme::dark(), serde_json::from_str(&json).unwrap());
+
+        let partial: Theme = serde_json::from_str(r#"{"warn": "bold red"}"#).unwrap();
+        assert_eq!(
+            vec!["warn"],
+            partial
+                .styles()
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(TextStyle::default(), partial.style(Role::Info));
+        let merged = Theme::dark().merge(partial);
+        assert_eq!(
+            TextStyle::from_sgr("31;1").unwrap(),
+            merged.style(Role::Warn)
+        );
+        for role in Role::ALL.iter().filter(|role| **role != Role::Warn) {
+            assert_eq!(Theme::dark().style(*role), merged.style(*role));
+        }
+
+        let error = serde_json::from_str::<Theme>(r#"{"loud": "bold"}"#).unwrap_err();
+        assert!(error.to_string().starts_with("unknown role 'loud'"));
+        let error = serde_json::from_str::<Theme>(r#"{"warn": "bold on"}"#).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("role 'warn': invalid style 'bold on'"));
+    }
+
+    #[test]
+    fn test_import_iterm2_and_windows_terminal() {
+        let plist = r##"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Ansi 1 Color</key>
+	<dict>
+		<key>Alpha Component</key>
+		<real>1</real>
+		<key>Blue Component</key>
+		<real>0.4</real>
+		<key>Color Space</key>
+		<string>sRGB</string>
+		<key>Green Component</key>
+		<real>0.4</real>
+		<key>Red Component</key>
+		<real>0.8</real>
+	</dict>
+	<key>Background Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>0.12941</real>
+		<key>Green Component</key>
+		<real>0.12157</real>
+		<key>Red Component</key>
+		<integer>0</integer>
+	</dict>
+</dict>
+</plist>"##;
+        let palette = import::iterm2(plist).unwrap();
+        assert_eq!(RGB(204, 102, 102), palette.colors[1]);
+        assert_eq!(RGB(0, 31, 33), palette.background);
+        assert_eq!(Palette::xterm().foreground, palette.foreground);
+
+        let settings = r##"{
+            // schemes of a settings.json
+            "schemes": [
+                {
+                    "name": "Campbell",
+                    "foreground": "#CCCCCC",
+                    "background": "#0C0C0C",
+                    "purple": "#881798",
+                    "brightBlue": "#3B78FF",
+                    "selectionBackground": "#FFFFFF",
+                },
+            ],
+        }"##;
+        let palette = import::parse(settings, import::Format::WindowsTerminal).unwrap();
+        assert_eq!(RGB(12, 12, 12), palette.background);
+        assert_eq!(RGB(136, 23, 152), palette.colors[5]);
+        assert_eq!(RGB(59, 120, 255), palette.colors[12]);
+
+        assert!(matches!(
+            import::windows_terminal("{\"red\": \"#ff\"}"),
+            Err(import::ImportError::InvalidColor { .. })
+        ));
+        assert!(matches!(
+            import::windows_terminal("{\n\"red\" \"#ff0000\"}"),
+            Err(import::ImportError::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            import::windows_terminal("{\"name\": \"empty\"}"),
+            Err(import::ImportError::NoColors)
+        ));
+    }
+
+    #[test]
+    fn test_import_alacritty() {
+        let toml = r##"
+[colors.primary]
+background = '#1d1f21' # comment
+foreground = "0xc5c8c6"
+
+[colors.normal]
+red = "#cc6666"
+
+[keyboard]
+bindings = [
+    { key = "N", mods = "Control", action = "SpawnNewInstance" },
+]
+
+[[colors.indexed_colors]]
+index = 16
+color = "#de935f"
+"##;
+        let palette = import::alacritty_toml(toml).unwrap();
+        assert_eq!(RGB(29, 31, 33), palette.background);
+        assert_eq!(RGB(197, 200, 198), palette.foreground);
+        assert_eq!(RGB(204, 102, 102), palette.colors[1]);
+        assert_eq!(RGB(222, 147, 95), palette.colors[16]);
+
+        let yaml = r##"
+colors:
+  primary:
+    background: '0x1d1f21'
+    foreground: '0xc5c8c6'
+  bright:
+    red: '#d54e53'
+  indexed_colors:
+    - { index: 16, color: '0xde935f' }
+    - index: 17
+      color: '0xd54e53'
+key_bindings:
+  - { key: V, mods: Control, action: Paste }
+"##;
+        let palette = import::alacritty_yaml(yaml).unwrap();
+        assert_eq!(RGB(29, 31, 33), palette.background);
+        assert_eq!(RGB(213, 78, 83), palette.colors[9]);
+        assert_eq!(RGB(222, 147, 95), palette.colors[16]);
+        assert_eq!(RGB(213, 78, 83), palette.colors[17]);
+
+        assert!(matches!(
+            import::alacritty_toml("[colors.normal]\nred: '#cc6666'"),
+            Err(import::ImportError::Syntax { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_import_kitty_xresources_and_base16() {
+        let palette = import::kitty("color17 tomato\nfont_size 12\n").unwrap();
+        assert_eq!(RGB(255, 99, 71), palette.colors[17]);
+        assert!(matches!(
+            import::kitty("font_size 12"),
+            Err(import::ImportError::NoColors)
+        ));
+
+        let xresources = "! Tomorrow Night\n\
+                          #define red #cc6666\n\
+                          *.foreground: rgb:c5/c8/c6\n\
+                          URxvt*color1: red\n";
+        let palette = import::parse(xresources, import::Format::Xresources).unwrap();
+        assert_eq!(RGB(197, 200, 198), palette.foreground);
+        assert_eq!(RGB(204, 102, 102), palette.colors[1]);
+
+        let base16 = "scheme: \"Test\"\n\
+                      base00: \"181818\"\nbase01: \"282828\"\nbase02: \"383838\"\n\
+                      base03: \"585858\"\nbase04: \"b8b8b8\"\nbase05: \"d8d8d8\"\n\
+                      base06: \"e8e8e8\"\nbase07: \"f8f8f8\"\nbase08: \"ab4642\"\n\
+                      base09: \"dc9656\"\nbase0A: \"f7ca88\"\nbase0B: \"a1b56c\"\n\
+                      base0C: \"86c1b9\"\nbase0D: \"7cafc2\"\nbase0E: \"ba8baf\"\n\
+                      base0F: \"a16946\"\n";
+        let path = std::path::Path::new("default-dark.yaml");
+        assert_eq!(
+            Some(import::Format::Base16),
+            import::Format::detect(path, base16)
+        );
+        let palette = import::parse(base16, import::Format::Base16).unwrap();
+        assert_eq!(RGB(24, 24, 24), palette.background);
+        assert_eq!(RGB(216, 216, 216), palette.foreground);
+        assert_eq!(RGB(171, 70, 66), palette.colors[9]);
+        assert_eq!(RGB(88, 88, 88), palette.colors[8]);
+
+        let partial = base16
+            .replace("base0D", "base0d")
+            .replace("base0E: \"ba8baf\"\n", "");
+        assert!(
+            matches!(import::base16(&partial), Err(import::ImportError::MissingColor(slot)) if slot == "base0E")
+        );
+    }
+
+    #[test]
+    fn test_export_round_trips() {
+        let mut palette = Palette::solarized_light();
+        palette.colors[100] = RGB(1, 2, 3);
+        let standard = Palette::tango();
+
+        assert_eq!(
+            palette,
+            import::xresources(&export::xresources(&palette)).unwrap()
+        );
+        assert_eq!(palette, import::kitty(&export::kitty(&palette)).unwrap());
+        assert_eq!(
+            palette,
+            import::alacritty_toml(&export::alacritty_toml(&palette)).unwrap()
+        );
+        assert_eq!(
+            standard,
+            import::windows_terminal(&export::windows_terminal(&standard, "Tango \"2\"")).unwrap()
+        );
+        assert_eq!(
+            standard,
+            import::iterm2(&export::iterm2(&standard)).unwrap()
+        );
+
+        let toml = export::alacritty_toml(&palette);
+        assert!(toml.contains("[colors.bright]\nblack = \"#002b36\"\n"));
+        assert!(toml.ends_with("[[colors.indexed_colors]]\nindex = 100\ncolor = \"#010203\"\n"));
+        let json = export::windows_terminal(&standard, "Tango \"2\"");
+        assert!(json.starts_with("{\n    \"name\": \"Tango \\\"2\\\"\",\n"));
+        assert!(json.contains("\"purple\": \"#75507b\",\n    \"brightPurple\": \"#ad7fa8\""));
+    }
+
+    #[test]
+    fn test_export_gimp_and_css() {
+        let palette = Palette::vga();
+        let gpl = export::gimp(&palette, "VGA");
+        assert!(
+            gpl.starts_with("GIMP Palette\nName: VGA\nColumns: 8\n#\n170 170 170\tforeground\n")
+        );
+        assert!(gpl.contains("\n170  85   0\tyellow\n"));
+        assert!(gpl.ends_with("255 255 255\tbright white\n"));
+
+        let css = export::css(&palette);
+        assert!(css.starts_with(":root {\n  --ansi-foreground: #aaaaaa;\n"));
+        assert!(css.ends_with("  --ansi-15: #ffffff;\n}\n"));
+
+        let theme = Theme::new()
+            .set(Role::Warn, "bold yellow on default".parse().unwrap())
+            .set(Role::Link, "ul strike #0000ee".parse().unwrap());
+        assert_eq!(
+            ":root {\n  --warn-color: #aa5500;\n  --warn-font-weight: bold;\n\
+             \x20 --link-color: #0000ee;\n  --link-text-decoration: underline line-through;\n}\n",
+            export::theme_css(&theme, &palette)
+        );
+    }
+
+    #[test]
+    fn test_dircolors_parsing() {
+        let colors = dircolors::LsColors::from_ls_colors(
+            "rs=0:di=01;34:ln=target:*.tar=01;31:*.JPG=35:*.jpg=36:*~=90",
+        )
+        .unwrap();
+        let bold_red = TextStyle::from_sgr("1;31").unwrap();
+        assert_eq!(Some(bold_red), colors.style_for_name("a.TAR"));
+        assert_eq!(
+            Some(TextStyle::from_sgr("35").unwrap()),
+            colors.style_for_name("photo.JPG")
+        );
+        assert_eq!(
+            Some(TextStyle::from_sgr("36").unwrap()),
+            colors.style_for_name("photo.jpg")
+        );
+        assert_eq!(None, colors.style_for_name("photo.Jpg"));
+        assert_eq!(
+            "di=34;1:ln=target:*.tar=31;1:*.JPG=35:*.jpg=36:*~=90",
+            colors.to_ls_colors()
+        );
+        assert_eq!(
+            colors,
+            dircolors::LsColors::from_ls_colors(&colors.to_ls_colors()).unwrap()
+        );
+
+        let gnu = dircolors::LsColors::gnu();
+        assert_eq!(Some(bold_red), gnu.style_for_name("crate-0.1.crate"));
+        assert_eq!(
+            Some(TextStyle::from_sgr("90").unwrap()),
+            gnu.style_for_name("notes.txt~")
+        );
+        assert_eq!(None, gnu.style_for_name("notes.txt"));
+        assert_eq!(
+            Some(TextStyle::from_sgr("30;42").unwrap()),
+            gnu.style(dircolors::Indicator::StickyOtherWritable)
+        );
+
+        assert_eq!(
+            Err(dircolors::DircolorsError::UnknownKey("zz".to_owned())),
+            dircolors::LsColors::from_ls_colors("di=01;34:zz=01")
+        );
+        let database = "DIR 01;34\n\
+                        TERM xterm*\n\
+                        COLORTERM ?*\n\
+                        EXEC 01;32\n\
+                        TERM linux\n\
+                        TERM vt[0-9]?[!0-9]\n\
+                        FIFO 33\n";
+        let for_term = |term, colorterm| {
+            dircolors::LsColors::from_dircolors_for(database, term, colorterm).unwrap()
+        };
+        let xterm = for_term("xterm-256color", "");
+        assert_eq!(
+            Some(TextStyle::from_sgr("1;34").unwrap()),
+            xterm.style(dircolors::Indicator::Directory)
+        );
+        assert!(xterm.style(dircolors::Indicator::Executable).is_some());
+        assert_eq!(None, xterm.style(dircolors::Indicator::Fifo));
+        let truecolor = for_term("dumb", "truecolor");
+        assert!(truecolor.style(dircolors::Indicator::Executable).is_some());
+        assert_eq!(None, truecolor.style(dircolors::Indicator::Fifo));
+        let linux = for_term("linux", "");
+        assert_eq!(None, linux.style(dircolors::Indicator::Executable));
+        assert!(linux.style(dircolors::Indicator::Fifo).is_some());
+        assert!(for_term("vt10x", "")
+            .style(dircolors::Indicator::Fifo)
+            .is_some());
+        assert_eq!(
+            None,
+            for_term("vt100", "").style(dircolors::Indicator::Fifo)
+        );
+        let dumb = for_term("dumb", "");
+        assert!(dumb.style(dircolors::Indicator::Directory).is_some());
+        assert_eq!(None, dumb.style(dircolors::Indicator::Executable));
+        assert_eq!(None, dumb.style(dircolors::Indicator::Fifo));
+
+        assert!(matches!(
+            dircolors::LsColors::from_dircolors("DIR 01;34\nEXEC\n"),
+            Err(dircolors::DircolorsError::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            dircolors::LsColors::from_ls_colors("di=01;x"),
+            Err(dircolors::DircolorsError::InvalidStyle { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dircolors_lookup() {
+        use dircolors::{Indicator, LsColors};
+        use std::fs;
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let root = std::env::temp_dir().join(format!("rustcolor-dircolors-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let archive = root.join("backup.tar");
+        fs::write(&archive, "").unwrap();
+        let script = root.join("run.sh");
+        fs::write(&script, "").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let shared = root.join("shared");
+        fs::create_dir_all(&shared).unwrap();
+        fs::set_permissions(&shared, fs::Permissions::from_mode(0o1777)).unwrap();
+        let orphan = root.join("orphan");
+        let _ = fs::remove_file(&orphan);
+        symlink(root.join("missing"), &orphan).unwrap();
+        let link = root.join("link");
+        let _ = fs::remove_file(&link);
+        symlink(&archive, &link).unwrap();
+
+        let gnu = LsColors::gnu();
+        let style_for = |colors: &LsColors, path: &std::path::Path| {
+            colors.style_for(path, &fs::symlink_metadata(path).unwrap())
+        };
+        assert_eq!(gnu.style_for_name("backup.tar"), style_for(&gnu, &archive));
+        assert_eq!(gnu.style(Indicator::Executable), style_for(&gnu, &script));
+        assert_eq!(
+            gnu.style(Indicator::StickyOtherWritable),
+            style_for(&gnu, &shared)
+        );
+        assert_eq!(gnu.style(Indicator::Directory), style_for(&gnu, &root));
+        assert_eq!(gnu.style(Indicator::Orphan), style_for(&gnu, &orphan));
+        assert_eq!(gnu.style(Indicator::Symlink), style_for(&gnu, &link));
+        let targets = gnu.clone().link_target();
+        assert_eq!(gnu.style_for_name("backup.tar"), style_for(&targets, &link));
+
+        let plain = LsColors::new().set(Indicator::Directory, TextStyle::from_sgr("34").unwrap());
+        assert_eq!(None, style_for(&plain, &shared.join("..").join("run.sh")));
+        assert_eq!(
+            "\u{001b}[34mshared\u{001b}[0m",
+            plain.paint(&shared, "shared")
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_capability_strings() {
+        use capabilities::{Capabilities, CapabilityError};
+
+        let grep = Capabilities::grep_defaults()
+            .merge(Capabilities::parse("mt=01;32:ms=04:rv:cx=").unwrap());
+        assert_eq!(Some(TextStyle::from_sgr("4").unwrap()), grep.style("ms"));
+        assert_eq!(Some(TextStyle::from_sgr("1;32").unwrap()), grep.style("mc"));
+        assert_eq!(Some(TextStyle::default()), grep.style("cx"));
+        assert_eq!(None, grep.style("rv"));
+        assert!(grep.flag("rv") && !grep.flag("ne"));
+        assert_eq!("\u{001b}[32m12\u{001b}[0m", grep.paint("ln", "12"));
+        assert_eq!(":", grep.paint("sl", ":"));
+
+        let gcc = Capabilities::gcc_defaults();
+        assert_eq!(
+            Some(TextStyle::from_sgr("1;35").unwrap()),
+            gcc.style("warning")
+        );
+        assert_eq!(
+            Some(TextStyle::from_sgr("32").unwrap()),
+            gcc.style("fixit-insert")
+        );
+        let custom = Capabilities::new().set("error", TextStyle::from_sgr("1;31").unwrap());
+        assert_eq!("error=31;1", custom.to_string());
+        assert_eq!(custom, Capabilities::parse(&custom.to_string()).unwrap());
+
+        assert_eq!(
+            Err(CapabilityError::MissingName("=01".to_owned())),
+            Capabilities::parse("fn=35:=01")
+        );
+        assert!(matches!(
+            Capabilities::parse("fn=3x"),
+            Err(CapabilityError::InvalidStyle { .. })
+        ));
+    }
+
+    #[test]
+    fn test_git_color_specs() {
+        let style = TextStyle::from_git("bold red ul").unwrap();
+        assert_eq!(TextStyle::from_sgr("1;4;31").unwrap(), style);
+
+        let style = TextStyle::from_git("reset brightblue #102030 italic no-italic dim").unwrap();
+        assert_eq!(Some(Color::C16(12)), style.foreground);
+        assert_eq!(Some(Color::Rgb(RGB(16, 32, 48))), style.background);
+        assert!(style.faint && !style.italic);
+
+        let style = TextStyle::from_git("normal default").unwrap();
+        assert_eq!(None, style.foreground);
+        assert_eq!(Some(Color::Default), style.background);
+
+        let style = TextStyle::from_git("-1 blue").unwrap();
+        assert_eq!(None, style.foreground);
+        assert_eq!(Some(Color::C16(4)), style.background);
+        assert_eq!(
+            TextStyle::from_git("bold green").unwrap(),
+            TextStyle::from_git("autoreset bold green").unwrap()
+        );
+
+        assert_eq!(
+            Err(ParseStyleError::UnexpectedColor("green".to_owned())),
+            TextStyle::from_git("red blue green")
+        );
+        assert_eq!(
+            Err(ParseStyleError::UnknownWord("shiny".to_owned())),
+            TextStyle::from_git("bold shiny")
+        );
+        assert_eq!(
+            Err(ParseStyleError::InvalidColor(
+                ParseColorError::InvalidComponent("300".to_owned())
+            )),
+            TextStyle::from_git("300")
+        );
+    }
+
+    #[test]
+    fn test_hyperlinks() {
+        use hyperlink::Hyperlink;
+
+        let link = Hyperlink::new("https://example.com/a b\u{7}").id("x:1;2");
+        assert_eq!(
+            "\u{001b}]8;id=x%3A1%3B2;https://example.com/a%20b%07\u{001b}\\",
+            link.open()
+        );
+        let tokens = parse(&link.wrap("go"));
+        assert_eq!(
+            vec![
+                Token::Osc("8;id=x%3A1%3B2;https://example.com/a%20b%07".to_owned()),
+                Token::Text("go".to_owned()),
+                Token::Osc("8;;".to_owned()),
+            ],
+            tokens
+        );
+
+        let file = Hyperlink::file(std::path::Path::new("/tmp/my file.rs"));
+        assert_eq!("file:///tmp/my%20file.rs", file.url());
+        #[cfg(windows)]
+        assert_eq!(
+            "file:///C:/Users/me/my%20file.rs",
+            Hyperlink::file(std::path::Path::new(r"C:\Users\me\my file.rs")).url()
+        );
+
+        let link = Hyperlink::new("https://example.com");
+        let expected = if hyperlink::enabled() {
+            link.wrap("linked")
+        } else {
+            "linked".to_owned()
+        };
+        assert_eq!(expected, link.render("linked"));
+    }
+
+    #[test]
+    fn test_strip_and_width() {
+        let output = format!(
+            "{} \u{4e16}\u{754c}\ne\u{301}\t{}",
+            "ok".info(),
+            hyperlink::Hyperlink::new("https://example.com").wrap(&"link".emphasis())
+        );
+        assert_eq!("ok \u{4e16}\u{754c}\ne\u{301}\tlink", strip(&output));
+        assert_eq!(7, width(&output));
+        assert_eq!(5, width("e\u{301}link"));
+        assert_eq!(
+            0,
+            width("\u{001b}]8;;https://example.com\u{001b}\\\u{001b}]8;;\u{001b}\\")
+        );
+    }
 }
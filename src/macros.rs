@@ -1,7 +1,8 @@
 /*!
 # macros
 This module implements macros to automate the printer module
-and remove the boilerplate when coding.
+and remove the boilerplate when coding. The styles come from the active theme, the
+colors described below being those of the default theme.
 */
 
 /// Prints in terminal an output with fg green and bg black.
@@ -59,3 +60,43 @@ macro_rules! underline {
         println!("{}", $text.underline());
     };
 }
+
+/// Prints in terminal an output with bold fg green.
+#[macro_export]
+macro_rules! success {
+    ($text:expr) => {
+        println!("{}", $text.success());
+    };
+}
+
+/// Prints in terminal an output with fg gray.
+#[macro_export]
+macro_rules! muted {
+    ($text:expr) => {
+        println!("{}", $text.muted());
+    };
+}
+
+/// Prints in terminal an output with bold text.
+#[macro_export]
+macro_rules! emphasis {
+    ($text:expr) => {
+        println!("{}", $text.emphasis());
+    };
+}
+
+/// Prints in terminal an output with fg cyan.
+#[macro_export]
+macro_rules! code {
+    ($text:expr) => {
+        println!("{}", $text.code());
+    };
+}
+
+/// Prints in terminal an output with fg blue and underline tag.
+#[macro_export]
+macro_rules! link {
+    ($text:expr) => {
+        println!("{}", $text.link());
+    };
+}
@@ -0,0 +1,894 @@
+/*!
+# names
+This module holds the named colors of the [CSS Color Module Level 4](https://www.w3.org/TR/css-color-4/#named-colors)
+and of the X11 `rgb.txt` database, so colors can be written as `"tomato"` or `"slate gray"`.
+
+Names are matched ignoring case, spaces and underscores. Where both sets define a name
+with different values, as `gray`, `green`, `maroon` and `purple`, the CSS value wins;
+the X11 value stays available through [`x11`].
+
+# Examples
+
+```
+use rustcolor::color::RGB;
+use rustcolor::names;
+
+assert_eq!(Some(RGB(255, 99, 71)), names::lookup("Tomato"));
+assert_eq!(Some(RGB(112, 128, 144)), names::lookup("slate gray"));
+assert_eq!(Some(RGB(190, 190, 190)), names::x11("gray"));
+assert_eq!("tomato", names::nearest(RGB(250, 100, 70)).0);
+```
+*/
+
+use crate::color::RGB;
+use crate::space::Lab;
+
+/// The CSS Color Module Level 4 named colors, sorted by name.
+pub const CSS_COLORS: &[(&str, RGB)] = &[
+    ("aliceblue", RGB(240, 248, 255)),
+    ("antiquewhite", RGB(250, 235, 215)),
+    ("aqua", RGB(0, 255, 255)),
+    ("aquamarine", RGB(127, 255, 212)),
+    ("azure", RGB(240, 255, 255)),
+    ("beige", RGB(245, 245, 220)),
+    ("bisque", RGB(255, 228, 196)),
+    ("black", RGB(0, 0, 0)),
+    ("blanchedalmond", RGB(255, 235, 205)),
+    ("blue", RGB(0, 0, 255)),
+    ("blueviolet", RGB(138, 43, 226)),
+    ("brown", RGB(165, 42, 42)),
+    ("burlywood", RGB(222, 184, 135)),
+    ("cadetblue", RGB(95, 158, 160)),
+    ("chartreuse", RGB(127, 255, 0)),
+    ("chocolate", RGB(210, 105, 30)),
+    ("coral", RGB(255, 127, 80)),
+    ("cornflowerblue", RGB(100, 149, 237)),
+    ("cornsilk", RGB(255, 248, 220)),
+    ("crimson", RGB(220, 20, 60)),
+    ("cyan", RGB(0, 255, 255)),
+    ("darkblue", RGB(0, 0, 139)),
+    ("darkcyan", RGB(0, 139, 139)),
+    ("darkgoldenrod", RGB(184, 134, 11)),
+    ("darkgray", RGB(169, 169, 169)),
+    ("darkgreen", RGB(0, 100, 0)),
+    ("darkgrey", RGB(169, 169, 169)),
+    ("darkkhaki", RGB(189, 183, 107)),
+    ("darkmagenta", RGB(139, 0, 139)),
+    ("darkolivegreen", RGB(85, 107, 47)),
+    ("darkorange", RGB(255, 140, 0)),
+    ("darkorchid", RGB(153, 50, 204)),
+    ("darkred", RGB(139, 0, 0)),
+    ("darksalmon", RGB(233, 150, 122)),
+    ("darkseagreen", RGB(143, 188, 143)),
+    ("darkslateblue", RGB(72, 61, 139)),
+    ("darkslategray", RGB(47, 79, 79)),
+    ("darkslategrey", RGB(47, 79, 79)),
+    ("darkturquoise", RGB(0, 206, 209)),
+    ("darkviolet", RGB(148, 0, 211)),
+    ("deeppink", RGB(255, 20, 147)),
+    ("deepskyblue", RGB(0, 191, 255)),
+    ("dimgray", RGB(105, 105, 105)),
+    ("dimgrey", RGB(105, 105, 105)),
+    ("dodgerblue", RGB(30, 144, 255)),
+    ("firebrick", RGB(178, 34, 34)),
+    ("floralwhite", RGB(255, 250, 240)),
+    ("forestgreen", RGB(34, 139, 34)),
+    ("fuchsia", RGB(255, 0, 255)),
+    ("gainsboro", RGB(220, 220, 220)),
+    ("ghostwhite", RGB(248, 248, 255)),
+    ("gold", RGB(255, 215, 0)),
+    ("goldenrod", RGB(218, 165, 32)),
+    ("gray", RGB(128, 128, 128)),
+    ("green", RGB(0, 128, 0)),
+    ("greenyellow", RGB(173, 255, 47)),
+    ("grey", RGB(128, 128, 128)),
+    ("honeydew", RGB(240, 255, 240)),
+    ("hotpink", RGB(255, 105, 180)),
+    ("indianred", RGB(205, 92, 92)),
+    ("indigo", RGB(75, 0, 130)),
+    ("ivory", RGB(255, 255, 240)),
+    ("khaki", RGB(240, 230, 140)),
+    ("lavender", RGB(230, 230, 250)),
+    ("lavenderblush", RGB(255, 240, 245)),
+    ("lawngreen", RGB(124, 252, 0)),
+    ("lemonchiffon", RGB(255, 250, 205)),
+    ("lightblue", RGB(173, 216, 230)),
+    ("lightcoral", RGB(240, 128, 128)),
+    ("lightcyan", RGB(224, 255, 255)),
+    ("lightgoldenrodyellow", RGB(250, 250, 210)),
+    ("lightgray", RGB(211, 211, 211)),
+    ("lightgreen", RGB(144, 238, 144)),
+    ("lightgrey", RGB(211, 211, 211)),
+    ("lightpink", RGB(255, 182, 193)),
+    ("lightsalmon", RGB(255, 160, 122)),
+    ("lightseagreen", RGB(32, 178, 170)),
+    ("lightskyblue", RGB(135, 206, 250)),
+    ("lightslategray", RGB(119, 136, 153)),
+    ("lightslategrey", RGB(119, 136, 153)),
+    ("lightsteelblue", RGB(176, 196, 222)),
+    ("lightyellow", RGB(255, 255, 224)),
+    ("lime", RGB(0, 255, 0)),
+    ("limegreen", RGB(50, 205, 50)),
+    ("linen", RGB(250, 240, 230)),
+    ("magenta", RGB(255, 0, 255)),
+    ("maroon", RGB(128, 0, 0)),
+    ("mediumaquamarine", RGB(102, 205, 170)),
+    ("mediumblue", RGB(0, 0, 205)),
+    ("mediumorchid", RGB(186, 85, 211)),
+    ("mediumpurple", RGB(147, 112, 219)),
+    ("mediumseagreen", RGB(60, 179, 113)),
+    ("mediumslateblue", RGB(123, 104, 238)),
+    ("mediumspringgreen", RGB(0, 250, 154)),
+    ("mediumturquoise", RGB(72, 209, 204)),
+    ("mediumvioletred", RGB(199, 21, 133)),
+    ("midnightblue", RGB(25, 25, 112)),
+    ("mintcream", RGB(245, 255, 250)),
+    ("mistyrose", RGB(255, 228, 225)),
+    ("moccasin", RGB(255, 228, 181)),
+    ("navajowhite", RGB(255, 222, 173)),
+    ("navy", RGB(0, 0, 128)),
+    ("oldlace", RGB(253, 245, 230)),
+    ("olive", RGB(128, 128, 0)),
+    ("olivedrab", RGB(107, 142, 35)),
+    ("orange", RGB(255, 165, 0)),
+    ("orangered", RGB(255, 69, 0)),
+    ("orchid", RGB(218, 112, 214)),
+    ("palegoldenrod", RGB(238, 232, 170)),
+    ("palegreen", RGB(152, 251, 152)),
+    ("paleturquoise", RGB(175, 238, 238)),
+    ("palevioletred", RGB(219, 112, 147)),
+    ("papayawhip", RGB(255, 239, 213)),
+    ("peachpuff", RGB(255, 218, 185)),
+    ("peru", RGB(205, 133, 63)),
+    ("pink", RGB(255, 192, 203)),
+    ("plum", RGB(221, 160, 221)),
+    ("powderblue", RGB(176, 224, 230)),
+    ("purple", RGB(128, 0, 128)),
+    ("rebeccapurple", RGB(102, 51, 153)),
+    ("red", RGB(255, 0, 0)),
+    ("rosybrown", RGB(188, 143, 143)),
+    ("royalblue", RGB(65, 105, 225)),
+    ("saddlebrown", RGB(139, 69, 19)),
+    ("salmon", RGB(250, 128, 114)),
+    ("sandybrown", RGB(244, 164, 96)),
+    ("seagreen", RGB(46, 139, 87)),
+    ("seashell", RGB(255, 245, 238)),
+    ("sienna", RGB(160, 82, 45)),
+    ("silver", RGB(192, 192, 192)),
+    ("skyblue", RGB(135, 206, 235)),
+    ("slateblue", RGB(106, 90, 205)),
+    ("slategray", RGB(112, 128, 144)),
+    ("slategrey", RGB(112, 128, 144)),
+    ("snow", RGB(255, 250, 250)),
+    ("springgreen", RGB(0, 255, 127)),
+    ("steelblue", RGB(70, 130, 180)),
+    ("tan", RGB(210, 180, 140)),
+    ("teal", RGB(0, 128, 128)),
+    ("thistle", RGB(216, 191, 216)),
+    ("tomato", RGB(255, 99, 71)),
+    ("turquoise", RGB(64, 224, 208)),
+    ("violet", RGB(238, 130, 238)),
+    ("wheat", RGB(245, 222, 179)),
+    ("white", RGB(255, 255, 255)),
+    ("whitesmoke", RGB(245, 245, 245)),
+    ("yellow", RGB(255, 255, 0)),
+    ("yellowgreen", RGB(154, 205, 50)),
+];
+
+/// The X11 `rgb.txt` colors, lowercase without spaces and sorted by name.
+pub const X11_COLORS: &[(&str, RGB)] = &[
+    ("aliceblue", RGB(240, 248, 255)),
+    ("antiquewhite", RGB(250, 235, 215)),
+    ("antiquewhite1", RGB(255, 239, 219)),
+    ("antiquewhite2", RGB(238, 223, 204)),
+    ("antiquewhite3", RGB(205, 192, 176)),
+    ("antiquewhite4", RGB(139, 131, 120)),
+    ("aquamarine", RGB(127, 255, 212)),
+    ("aquamarine1", RGB(127, 255, 212)),
+    ("aquamarine2", RGB(118, 238, 198)),
+    ("aquamarine3", RGB(102, 205, 170)),
+    ("aquamarine4", RGB(69, 139, 116)),
+    ("azure", RGB(240, 255, 255)),
+    ("azure1", RGB(240, 255, 255)),
+    ("azure2", RGB(224, 238, 238)),
+    ("azure3", RGB(193, 205, 205)),
+    ("azure4", RGB(131, 139, 139)),
+    ("beige", RGB(245, 245, 220)),
+    ("bisque", RGB(255, 228, 196)),
+    ("bisque1", RGB(255, 228, 196)),
+    ("bisque2", RGB(238, 213, 183)),
+    ("bisque3", RGB(205, 183, 158)),
+    ("bisque4", RGB(139, 125, 107)),
+    ("black", RGB(0, 0, 0)),
+    ("blanchedalmond", RGB(255, 235, 205)),
+    ("blue", RGB(0, 0, 255)),
+    ("blue1", RGB(0, 0, 255)),
+    ("blue2", RGB(0, 0, 238)),
+    ("blue3", RGB(0, 0, 205)),
+    ("blue4", RGB(0, 0, 139)),
+    ("blueviolet", RGB(138, 43, 226)),
+    ("brown", RGB(165, 42, 42)),
+    ("brown1", RGB(255, 64, 64)),
+    ("brown2", RGB(238, 59, 59)),
+    ("brown3", RGB(205, 51, 51)),
+    ("brown4", RGB(139, 35, 35)),
+    ("burlywood", RGB(222, 184, 135)),
+    ("burlywood1", RGB(255, 211, 155)),
+    ("burlywood2", RGB(238, 197, 145)),
+    ("burlywood3", RGB(205, 170, 125)),
+    ("burlywood4", RGB(139, 115, 85)),
+    ("cadetblue", RGB(95, 158, 160)),
+    ("cadetblue1", RGB(152, 245, 255)),
+    ("cadetblue2", RGB(142, 229, 238)),
+    ("cadetblue3", RGB(122, 197, 205)),
+    ("cadetblue4", RGB(83, 134, 139)),
+    ("chartreuse", RGB(127, 255, 0)),
+    ("chartreuse1", RGB(127, 255, 0)),
+    ("chartreuse2", RGB(118, 238, 0)),
+    ("chartreuse3", RGB(102, 205, 0)),
+    ("chartreuse4", RGB(69, 139, 0)),
+    ("chocolate", RGB(210, 105, 30)),
+    ("chocolate1", RGB(255, 127, 36)),
+    ("chocolate2", RGB(238, 118, 33)),
+    ("chocolate3", RGB(205, 102, 29)),
+    ("chocolate4", RGB(139, 69, 19)),
+    ("coral", RGB(255, 127, 80)),
+    ("coral1", RGB(255, 114, 86)),
+    ("coral2", RGB(238, 106, 80)),
+    ("coral3", RGB(205, 91, 69)),
+    ("coral4", RGB(139, 62, 47)),
+    ("cornflowerblue", RGB(100, 149, 237)),
+    ("cornsilk", RGB(255, 248, 220)),
+    ("cornsilk1", RGB(255, 248, 220)),
+    ("cornsilk2", RGB(238, 232, 205)),
+    ("cornsilk3", RGB(205, 200, 177)),
+    ("cornsilk4", RGB(139, 136, 120)),
+    ("cyan", RGB(0, 255, 255)),
+    ("cyan1", RGB(0, 255, 255)),
+    ("cyan2", RGB(0, 238, 238)),
+    ("cyan3", RGB(0, 205, 205)),
+    ("cyan4", RGB(0, 139, 139)),
+    ("darkblue", RGB(0, 0, 139)),
+    ("darkcyan", RGB(0, 139, 139)),
+    ("darkgoldenrod", RGB(184, 134, 11)),
+    ("darkgoldenrod1", RGB(255, 185, 15)),
+    ("darkgoldenrod2", RGB(238, 173, 14)),
+    ("darkgoldenrod3", RGB(205, 149, 12)),
+    ("darkgoldenrod4", RGB(139, 101, 8)),
+    ("darkgray", RGB(169, 169, 169)),
+    ("darkgreen", RGB(0, 100, 0)),
+    ("darkgrey", RGB(169, 169, 169)),
+    ("darkkhaki", RGB(189, 183, 107)),
+    ("darkmagenta", RGB(139, 0, 139)),
+    ("darkolivegreen", RGB(85, 107, 47)),
+    ("darkolivegreen1", RGB(202, 255, 112)),
+    ("darkolivegreen2", RGB(188, 238, 104)),
+    ("darkolivegreen3", RGB(162, 205, 90)),
+    ("darkolivegreen4", RGB(110, 139, 61)),
+    ("darkorange", RGB(255, 140, 0)),
+    ("darkorange1", RGB(255, 127, 0)),
+    ("darkorange2", RGB(238, 118, 0)),
+    ("darkorange3", RGB(205, 102, 0)),
+    ("darkorange4", RGB(139, 69, 0)),
+    ("darkorchid", RGB(153, 50, 204)),
+    ("darkorchid1", RGB(191, 62, 255)),
+    ("darkorchid2", RGB(178, 58, 238)),
+    ("darkorchid3", RGB(154, 50, 205)),
+    ("darkorchid4", RGB(104, 34, 139)),
+    ("darkred", RGB(139, 0, 0)),
+    ("darksalmon", RGB(233, 150, 122)),
+    ("darkseagreen", RGB(143, 188, 143)),
+    ("darkseagreen1", RGB(193, 255, 193)),
+    ("darkseagreen2", RGB(180, 238, 180)),
+    ("darkseagreen3", RGB(155, 205, 155)),
+    ("darkseagreen4", RGB(105, 139, 105)),
+    ("darkslateblue", RGB(72, 61, 139)),
+    ("darkslategray", RGB(47, 79, 79)),
+    ("darkslategray1", RGB(151, 255, 255)),
+    ("darkslategray2", RGB(141, 238, 238)),
+    ("darkslategray3", RGB(121, 205, 205)),
+    ("darkslategray4", RGB(82, 139, 139)),
+    ("darkslategrey", RGB(47, 79, 79)),
+    ("darkturquoise", RGB(0, 206, 209)),
+    ("darkviolet", RGB(148, 0, 211)),
+    ("debianred", RGB(215, 7, 81)),
+    ("deeppink", RGB(255, 20, 147)),
+    ("deeppink1", RGB(255, 20, 147)),
+    ("deeppink2", RGB(238, 18, 137)),
+    ("deeppink3", RGB(205, 16, 118)),
+    ("deeppink4", RGB(139, 10, 80)),
+    ("deepskyblue", RGB(0, 191, 255)),
+    ("deepskyblue1", RGB(0, 191, 255)),
+    ("deepskyblue2", RGB(0, 178, 238)),
+    ("deepskyblue3", RGB(0, 154, 205)),
+    ("deepskyblue4", RGB(0, 104, 139)),
+    ("dimgray", RGB(105, 105, 105)),
+    ("dimgrey", RGB(105, 105, 105)),
+    ("dodgerblue", RGB(30, 144, 255)),
+    ("dodgerblue1", RGB(30, 144, 255)),
+    ("dodgerblue2", RGB(28, 134, 238)),
+    ("dodgerblue3", RGB(24, 116, 205)),
+    ("dodgerblue4", RGB(16, 78, 139)),
+    ("firebrick", RGB(178, 34, 34)),
+    ("firebrick1", RGB(255, 48, 48)),
+    ("firebrick2", RGB(238, 44, 44)),
+    ("firebrick3", RGB(205, 38, 38)),
+    ("firebrick4", RGB(139, 26, 26)),
+    ("floralwhite", RGB(255, 250, 240)),
+    ("forestgreen", RGB(34, 139, 34)),
+    ("gainsboro", RGB(220, 220, 220)),
+    ("ghostwhite", RGB(248, 248, 255)),
+    ("gold", RGB(255, 215, 0)),
+    ("gold1", RGB(255, 215, 0)),
+    ("gold2", RGB(238, 201, 0)),
+    ("gold3", RGB(205, 173, 0)),
+    ("gold4", RGB(139, 117, 0)),
+    ("goldenrod", RGB(218, 165, 32)),
+    ("goldenrod1", RGB(255, 193, 37)),
+    ("goldenrod2", RGB(238, 180, 34)),
+    ("goldenrod3", RGB(205, 155, 29)),
+    ("goldenrod4", RGB(139, 105, 20)),
+    ("gray", RGB(190, 190, 190)),
+    ("gray0", RGB(0, 0, 0)),
+    ("gray1", RGB(3, 3, 3)),
+    ("gray10", RGB(26, 26, 26)),
+    ("gray100", RGB(255, 255, 255)),
+    ("gray11", RGB(28, 28, 28)),
+    ("gray12", RGB(31, 31, 31)),
+    ("gray13", RGB(33, 33, 33)),
+    ("gray14", RGB(36, 36, 36)),
+    ("gray15", RGB(38, 38, 38)),
+    ("gray16", RGB(41, 41, 41)),
+    ("gray17", RGB(43, 43, 43)),
+    ("gray18", RGB(46, 46, 46)),
+    ("gray19", RGB(48, 48, 48)),
+    ("gray2", RGB(5, 5, 5)),
+    ("gray20", RGB(51, 51, 51)),
+    ("gray21", RGB(54, 54, 54)),
+    ("gray22", RGB(56, 56, 56)),
+    ("gray23", RGB(59, 59, 59)),
+    ("gray24", RGB(61, 61, 61)),
+    ("gray25", RGB(64, 64, 64)),
+    ("gray26", RGB(66, 66, 66)),
+    ("gray27", RGB(69, 69, 69)),
+    ("gray28", RGB(71, 71, 71)),
+    ("gray29", RGB(74, 74, 74)),
+    ("gray3", RGB(8, 8, 8)),
+    ("gray30", RGB(77, 77, 77)),
+    ("gray31", RGB(79, 79, 79)),
+    ("gray32", RGB(82, 82, 82)),
+    ("gray33", RGB(84, 84, 84)),
+    ("gray34", RGB(87, 87, 87)),
+    ("gray35", RGB(89, 89, 89)),
+    ("gray36", RGB(92, 92, 92)),
+    ("gray37", RGB(94, 94, 94)),
+    ("gray38", RGB(97, 97, 97)),
+    ("gray39", RGB(99, 99, 99)),
+    ("gray4", RGB(10, 10, 10)),
+    ("gray40", RGB(102, 102, 102)),
+    ("gray41", RGB(105, 105, 105)),
+    ("gray42", RGB(107, 107, 107)),
+    ("gray43", RGB(110, 110, 110)),
+    ("gray44", RGB(112, 112, 112)),
+    ("gray45", RGB(115, 115, 115)),
+    ("gray46", RGB(117, 117, 117)),
+    ("gray47", RGB(120, 120, 120)),
+    ("gray48", RGB(122, 122, 122)),
+    ("gray49", RGB(125, 125, 125)),
+    ("gray5", RGB(13, 13, 13)),
+    ("gray50", RGB(127, 127, 127)),
+    ("gray51", RGB(130, 130, 130)),
+    ("gray52", RGB(133, 133, 133)),
+    ("gray53", RGB(135, 135, 135)),
+    ("gray54", RGB(138, 138, 138)),
+    ("gray55", RGB(140, 140, 140)),
+    ("gray56", RGB(143, 143, 143)),
+    ("gray57", RGB(145, 145, 145)),
+    ("gray58", RGB(148, 148, 148)),
+    ("gray59", RGB(150, 150, 150)),
+    ("gray6", RGB(15, 15, 15)),
+    ("gray60", RGB(153, 153, 153)),
+    ("gray61", RGB(156, 156, 156)),
+    ("gray62", RGB(158, 158, 158)),
+    ("gray63", RGB(161, 161, 161)),
+    ("gray64", RGB(163, 163, 163)),
+    ("gray65", RGB(166, 166, 166)),
+    ("gray66", RGB(168, 168, 168)),
+    ("gray67", RGB(171, 171, 171)),
+    ("gray68", RGB(173, 173, 173)),
+    ("gray69", RGB(176, 176, 176)),
+    ("gray7", RGB(18, 18, 18)),
+    ("gray70", RGB(179, 179, 179)),
+    ("gray71", RGB(181, 181, 181)),
+    ("gray72", RGB(184, 184, 184)),
+    ("gray73", RGB(186, 186, 186)),
+    ("gray74", RGB(189, 189, 189)),
+    ("gray75", RGB(191, 191, 191)),
+    ("gray76", RGB(194, 194, 194)),
+    ("gray77", RGB(196, 196, 196)),
+    ("gray78", RGB(199, 199, 199)),
+    ("gray79", RGB(201, 201, 201)),
+    ("gray8", RGB(20, 20, 20)),
+    ("gray80", RGB(204, 204, 204)),
+    ("gray81", RGB(207, 207, 207)),
+    ("gray82", RGB(209, 209, 209)),
+    ("gray83", RGB(212, 212, 212)),
+    ("gray84", RGB(214, 214, 214)),
+    ("gray85", RGB(217, 217, 217)),
+    ("gray86", RGB(219, 219, 219)),
+    ("gray87", RGB(222, 222, 222)),
+    ("gray88", RGB(224, 224, 224)),
+    ("gray89", RGB(227, 227, 227)),
+    ("gray9", RGB(23, 23, 23)),
+    ("gray90", RGB(229, 229, 229)),
+    ("gray91", RGB(232, 232, 232)),
+    ("gray92", RGB(235, 235, 235)),
+    ("gray93", RGB(237, 237, 237)),
+    ("gray94", RGB(240, 240, 240)),
+    ("gray95", RGB(242, 242, 242)),
+    ("gray96", RGB(245, 245, 245)),
+    ("gray97", RGB(247, 247, 247)),
+    ("gray98", RGB(250, 250, 250)),
+    ("gray99", RGB(252, 252, 252)),
+    ("green", RGB(0, 255, 0)),
+    ("green1", RGB(0, 255, 0)),
+    ("green2", RGB(0, 238, 0)),
+    ("green3", RGB(0, 205, 0)),
+    ("green4", RGB(0, 139, 0)),
+    ("greenyellow", RGB(173, 255, 47)),
+    ("grey", RGB(190, 190, 190)),
+    ("grey0", RGB(0, 0, 0)),
+    ("grey1", RGB(3, 3, 3)),
+    ("grey10", RGB(26, 26, 26)),
+    ("grey100", RGB(255, 255, 255)),
+    ("grey11", RGB(28, 28, 28)),
+    ("grey12", RGB(31, 31, 31)),
+    ("grey13", RGB(33, 33, 33)),
+    ("grey14", RGB(36, 36, 36)),
+    ("grey15", RGB(38, 38, 38)),
+    ("grey16", RGB(41, 41, 41)),
+    ("grey17", RGB(43, 43, 43)),
+    ("grey18", RGB(46, 46, 46)),
+    ("grey19", RGB(48, 48, 48)),
+    ("grey2", RGB(5, 5, 5)),
+    ("grey20", RGB(51, 51, 51)),
+    ("grey21", RGB(54, 54, 54)),
+    ("grey22", RGB(56, 56, 56)),
+    ("grey23", RGB(59, 59, 59)),
+    ("grey24", RGB(61, 61, 61)),
+    ("grey25", RGB(64, 64, 64)),
+    ("grey26", RGB(66, 66, 66)),
+    ("grey27", RGB(69, 69, 69)),
+    ("grey28", RGB(71, 71, 71)),
+    ("grey29", RGB(74, 74, 74)),
+    ("grey3", RGB(8, 8, 8)),
+    ("grey30", RGB(77, 77, 77)),
+    ("grey31", RGB(79, 79, 79)),
+    ("grey32", RGB(82, 82, 82)),
+    ("grey33", RGB(84, 84, 84)),
+    ("grey34", RGB(87, 87, 87)),
+    ("grey35", RGB(89, 89, 89)),
+    ("grey36", RGB(92, 92, 92)),
+    ("grey37", RGB(94, 94, 94)),
+    ("grey38", RGB(97, 97, 97)),
+    ("grey39", RGB(99, 99, 99)),
+    ("grey4", RGB(10, 10, 10)),
+    ("grey40", RGB(102, 102, 102)),
+    ("grey41", RGB(105, 105, 105)),
+    ("grey42", RGB(107, 107, 107)),
+    ("grey43", RGB(110, 110, 110)),
+    ("grey44", RGB(112, 112, 112)),
+    ("grey45", RGB(115, 115, 115)),
+    ("grey46", RGB(117, 117, 117)),
+    ("grey47", RGB(120, 120, 120)),
+    ("grey48", RGB(122, 122, 122)),
+    ("grey49", RGB(125, 125, 125)),
+    ("grey5", RGB(13, 13, 13)),
+    ("grey50", RGB(127, 127, 127)),
+    ("grey51", RGB(130, 130, 130)),
+    ("grey52", RGB(133, 133, 133)),
+    ("grey53", RGB(135, 135, 135)),
+    ("grey54", RGB(138, 138, 138)),
+    ("grey55", RGB(140, 140, 140)),
+    ("grey56", RGB(143, 143, 143)),
+    ("grey57", RGB(145, 145, 145)),
+    ("grey58", RGB(148, 148, 148)),
+    ("grey59", RGB(150, 150, 150)),
+    ("grey6", RGB(15, 15, 15)),
+    ("grey60", RGB(153, 153, 153)),
+    ("grey61", RGB(156, 156, 156)),
+    ("grey62", RGB(158, 158, 158)),
+    ("grey63", RGB(161, 161, 161)),
+    ("grey64", RGB(163, 163, 163)),
+    ("grey65", RGB(166, 166, 166)),
+    ("grey66", RGB(168, 168, 168)),
+    ("grey67", RGB(171, 171, 171)),
+    ("grey68", RGB(173, 173, 173)),
+    ("grey69", RGB(176, 176, 176)),
+    ("grey7", RGB(18, 18, 18)),
+    ("grey70", RGB(179, 179, 179)),
+    ("grey71", RGB(181, 181, 181)),
+    ("grey72", RGB(184, 184, 184)),
+    ("grey73", RGB(186, 186, 186)),
+    ("grey74", RGB(189, 189, 189)),
+    ("grey75", RGB(191, 191, 191)),
+    ("grey76", RGB(194, 194, 194)),
+    ("grey77", RGB(196, 196, 196)),
+    ("grey78", RGB(199, 199, 199)),
+    ("grey79", RGB(201, 201, 201)),
+    ("grey8", RGB(20, 20, 20)),
+    ("grey80", RGB(204, 204, 204)),
+    ("grey81", RGB(207, 207, 207)),
+    ("grey82", RGB(209, 209, 209)),
+    ("grey83", RGB(212, 212, 212)),
+    ("grey84", RGB(214, 214, 214)),
+    ("grey85", RGB(217, 217, 217)),
+    ("grey86", RGB(219, 219, 219)),
+    ("grey87", RGB(222, 222, 222)),
+    ("grey88", RGB(224, 224, 224)),
+    ("grey89", RGB(227, 227, 227)),
+    ("grey9", RGB(23, 23, 23)),
+    ("grey90", RGB(229, 229, 229)),
+    ("grey91", RGB(232, 232, 232)),
+    ("grey92", RGB(235, 235, 235)),
+    ("grey93", RGB(237, 237, 237)),
+    ("grey94", RGB(240, 240, 240)),
+    ("grey95", RGB(242, 242, 242)),
+    ("grey96", RGB(245, 245, 245)),
+    ("grey97", RGB(247, 247, 247)),
+    ("grey98", RGB(250, 250, 250)),
+    ("grey99", RGB(252, 252, 252)),
+    ("honeydew", RGB(240, 255, 240)),
+    ("honeydew1", RGB(240, 255, 240)),
+    ("honeydew2", RGB(224, 238, 224)),
+    ("honeydew3", RGB(193, 205, 193)),
+    ("honeydew4", RGB(131, 139, 131)),
+    ("hotpink", RGB(255, 105, 180)),
+    ("hotpink1", RGB(255, 110, 180)),
+    ("hotpink2", RGB(238, 106, 167)),
+    ("hotpink3", RGB(205, 96, 144)),
+    ("hotpink4", RGB(139, 58, 98)),
+    ("indianred", RGB(205, 92, 92)),
+    ("indianred1", RGB(255, 106, 106)),
+    ("indianred2", RGB(238, 99, 99)),
+    ("indianred3", RGB(205, 85, 85)),
+    ("indianred4", RGB(139, 58, 58)),
+    ("ivory", RGB(255, 255, 240)),
+    ("ivory1", RGB(255, 255, 240)),
+    ("ivory2", RGB(238, 238, 224)),
+    ("ivory3", RGB(205, 205, 193)),
+    ("ivory4", RGB(139, 139, 131)),
+    ("khaki", RGB(240, 230, 140)),
+    ("khaki1", RGB(255, 246, 143)),
+    ("khaki2", RGB(238, 230, 133)),
+    ("khaki3", RGB(205, 198, 115)),
+    ("khaki4", RGB(139, 134, 78)),
+    ("lavender", RGB(230, 230, 250)),
+    ("lavenderblush", RGB(255, 240, 245)),
+    ("lavenderblush1", RGB(255, 240, 245)),
+    ("lavenderblush2", RGB(238, 224, 229)),
+    ("lavenderblush3", RGB(205, 193, 197)),
+    ("lavenderblush4", RGB(139, 131, 134)),
+    ("lawngreen", RGB(124, 252, 0)),
+    ("lemonchiffon", RGB(255, 250, 205)),
+    ("lemonchiffon1", RGB(255, 250, 205)),
+    ("lemonchiffon2", RGB(238, 233, 191)),
+    ("lemonchiffon3", RGB(205, 201, 165)),
+    ("lemonchiffon4", RGB(139, 137, 112)),
+    ("lightblue", RGB(173, 216, 230)),
+    ("lightblue1", RGB(191, 239, 255)),
+    ("lightblue2", RGB(178, 223, 238)),
+    ("lightblue3", RGB(154, 192, 205)),
+    ("lightblue4", RGB(104, 131, 139)),
+    ("lightcoral", RGB(240, 128, 128)),
+    ("lightcyan", RGB(224, 255, 255)),
+    ("lightcyan1", RGB(224, 255, 255)),
+    ("lightcyan2", RGB(209, 238, 238)),
+    ("lightcyan3", RGB(180, 205, 205)),
+    ("lightcyan4", RGB(122, 139, 139)),
+    ("lightgoldenrod", RGB(238, 221, 130)),
+    ("lightgoldenrod1", RGB(255, 236, 139)),
+    ("lightgoldenrod2", RGB(238, 220, 130)),
+    ("lightgoldenrod3", RGB(205, 190, 112)),
+    ("lightgoldenrod4", RGB(139, 129, 76)),
+    ("lightgoldenrodyellow", RGB(250, 250, 210)),
+    ("lightgray", RGB(211, 211, 211)),
+    ("lightgreen", RGB(144, 238, 144)),
+    ("lightgrey", RGB(211, 211, 211)),
+    ("lightpink", RGB(255, 182, 193)),
+    ("lightpink1", RGB(255, 174, 185)),
+    ("lightpink2", RGB(238, 162, 173)),
+    ("lightpink3", RGB(205, 140, 149)),
+    ("lightpink4", RGB(139, 95, 101)),
+    ("lightsalmon", RGB(255, 160, 122)),
+    ("lightsalmon1", RGB(255, 160, 122)),
+    ("lightsalmon2", RGB(238, 149, 114)),
+    ("lightsalmon3", RGB(205, 129, 98)),
+    ("lightsalmon4", RGB(139, 87, 66)),
+    ("lightseagreen", RGB(32, 178, 170)),
+    ("lightskyblue", RGB(135, 206, 250)),
+    ("lightskyblue1", RGB(176, 226, 255)),
+    ("lightskyblue2", RGB(164, 211, 238)),
+    ("lightskyblue3", RGB(141, 182, 205)),
+    ("lightskyblue4", RGB(96, 123, 139)),
+    ("lightslateblue", RGB(132, 112, 255)),
+    ("lightslategray", RGB(119, 136, 153)),
+    ("lightslategrey", RGB(119, 136, 153)),
+    ("lightsteelblue", RGB(176, 196, 222)),
+    ("lightsteelblue1", RGB(202, 225, 255)),
+    ("lightsteelblue2", RGB(188, 210, 238)),
+    ("lightsteelblue3", RGB(162, 181, 205)),
+    ("lightsteelblue4", RGB(110, 123, 139)),
+    ("lightyellow", RGB(255, 255, 224)),
+    ("lightyellow1", RGB(255, 255, 224)),
+    ("lightyellow2", RGB(238, 238, 209)),
+    ("lightyellow3", RGB(205, 205, 180)),
+    ("lightyellow4", RGB(139, 139, 122)),
+    ("limegreen", RGB(50, 205, 50)),
+    ("linen", RGB(250, 240, 230)),
+    ("magenta", RGB(255, 0, 255)),
+    ("magenta1", RGB(255, 0, 255)),
+    ("magenta2", RGB(238, 0, 238)),
+    ("magenta3", RGB(205, 0, 205)),
+    ("magenta4", RGB(139, 0, 139)),
+    ("maroon", RGB(176, 48, 96)),
+    ("maroon1", RGB(255, 52, 179)),
+    ("maroon2", RGB(238, 48, 167)),
+    ("maroon3", RGB(205, 41, 144)),
+    ("maroon4", RGB(139, 28, 98)),
+    ("mediumaquamarine", RGB(102, 205, 170)),
+    ("mediumblue", RGB(0, 0, 205)),
+    ("mediumorchid", RGB(186, 85, 211)),
+    ("mediumorchid1", RGB(224, 102, 255)),
+    ("mediumorchid2", RGB(209, 95, 238)),
+    ("mediumorchid3", RGB(180, 82, 205)),
+    ("mediumorchid4", RGB(122, 55, 139)),
+    ("mediumpurple", RGB(147, 112, 219)),
+    ("mediumpurple1", RGB(171, 130, 255)),
+    ("mediumpurple2", RGB(159, 121, 238)),
+    ("mediumpurple3", RGB(137, 104, 205)),
+    ("mediumpurple4", RGB(93, 71, 139)),
+    ("mediumseagreen", RGB(60, 179, 113)),
+    ("mediumslateblue", RGB(123, 104, 238)),
+    ("mediumspringgreen", RGB(0, 250, 154)),
+    ("mediumturquoise", RGB(72, 209, 204)),
+    ("mediumvioletred", RGB(199, 21, 133)),
+    ("midnightblue", RGB(25, 25, 112)),
+    ("mintcream", RGB(245, 255, 250)),
+    ("mistyrose", RGB(255, 228, 225)),
+    ("mistyrose1", RGB(255, 228, 225)),
+    ("mistyrose2", RGB(238, 213, 210)),
+    ("mistyrose3", RGB(205, 183, 181)),
+    ("mistyrose4", RGB(139, 125, 123)),
+    ("moccasin", RGB(255, 228, 181)),
+    ("navajowhite", RGB(255, 222, 173)),
+    ("navajowhite1", RGB(255, 222, 173)),
+    ("navajowhite2", RGB(238, 207, 161)),
+    ("navajowhite3", RGB(205, 179, 139)),
+    ("navajowhite4", RGB(139, 121, 94)),
+    ("navy", RGB(0, 0, 128)),
+    ("navyblue", RGB(0, 0, 128)),
+    ("oldlace", RGB(253, 245, 230)),
+    ("olivedrab", RGB(107, 142, 35)),
+    ("olivedrab1", RGB(192, 255, 62)),
+    ("olivedrab2", RGB(179, 238, 58)),
+    ("olivedrab3", RGB(154, 205, 50)),
+    ("olivedrab4", RGB(105, 139, 34)),
+    ("orange", RGB(255, 165, 0)),
+    ("orange1", RGB(255, 165, 0)),
+    ("orange2", RGB(238, 154, 0)),
+    ("orange3", RGB(205, 133, 0)),
+    ("orange4", RGB(139, 90, 0)),
+    ("orangered", RGB(255, 69, 0)),
+    ("orangered1", RGB(255, 69, 0)),
+    ("orangered2", RGB(238, 64, 0)),
+    ("orangered3", RGB(205, 55, 0)),
+    ("orangered4", RGB(139, 37, 0)),
+    ("orchid", RGB(218, 112, 214)),
+    ("orchid1", RGB(255, 131, 250)),
+    ("orchid2", RGB(238, 122, 233)),
+    ("orchid3", RGB(205, 105, 201)),
+    ("orchid4", RGB(139, 71, 137)),
+    ("palegoldenrod", RGB(238, 232, 170)),
+    ("palegreen", RGB(152, 251, 152)),
+    ("palegreen1", RGB(154, 255, 154)),
+    ("palegreen2", RGB(144, 238, 144)),
+    ("palegreen3", RGB(124, 205, 124)),
+    ("palegreen4", RGB(84, 139, 84)),
+    ("paleturquoise", RGB(175, 238, 238)),
+    ("paleturquoise1", RGB(187, 255, 255)),
+    ("paleturquoise2", RGB(174, 238, 238)),
+    ("paleturquoise3", RGB(150, 205, 205)),
+    ("paleturquoise4", RGB(102, 139, 139)),
+    ("palevioletred", RGB(219, 112, 147)),
+    ("palevioletred1", RGB(255, 130, 171)),
+    ("palevioletred2", RGB(238, 121, 159)),
+    ("palevioletred3", RGB(205, 104, 137)),
+    ("palevioletred4", RGB(139, 71, 93)),
+    ("papayawhip", RGB(255, 239, 213)),
+    ("peachpuff", RGB(255, 218, 185)),
+    ("peachpuff1", RGB(255, 218, 185)),
+    ("peachpuff2", RGB(238, 203, 173)),
+    ("peachpuff3", RGB(205, 175, 149)),
+    ("peachpuff4", RGB(139, 119, 101)),
+    ("peru", RGB(205, 133, 63)),
+    ("pink", RGB(255, 192, 203)),
+    ("pink1", RGB(255, 181, 197)),
+    ("pink2", RGB(238, 169, 184)),
+    ("pink3", RGB(205, 145, 158)),
+    ("pink4", RGB(139, 99, 108)),
+    ("plum", RGB(221, 160, 221)),
+    ("plum1", RGB(255, 187, 255)),
+    ("plum2", RGB(238, 174, 238)),
+    ("plum3", RGB(205, 150, 205)),
+    ("plum4", RGB(139, 102, 139)),
+    ("powderblue", RGB(176, 224, 230)),
+    ("purple", RGB(160, 32, 240)),
+    ("purple1", RGB(155, 48, 255)),
+    ("purple2", RGB(145, 44, 238)),
+    ("purple3", RGB(125, 38, 205)),
+    ("purple4", RGB(85, 26, 139)),
+    ("red", RGB(255, 0, 0)),
+    ("red1", RGB(255, 0, 0)),
+    ("red2", RGB(238, 0, 0)),
+    ("red3", RGB(205, 0, 0)),
+    ("red4", RGB(139, 0, 0)),
+    ("rosybrown", RGB(188, 143, 143)),
+    ("rosybrown1", RGB(255, 193, 193)),
+    ("rosybrown2", RGB(238, 180, 180)),
+    ("rosybrown3", RGB(205, 155, 155)),
+    ("rosybrown4", RGB(139, 105, 105)),
+    ("royalblue", RGB(65, 105, 225)),
+    ("royalblue1", RGB(72, 118, 255)),
+    ("royalblue2", RGB(67, 110, 238)),
+    ("royalblue3", RGB(58, 95, 205)),
+    ("royalblue4", RGB(39, 64, 139)),
+    ("saddlebrown", RGB(139, 69, 19)),
+    ("salmon", RGB(250, 128, 114)),
+    ("salmon1", RGB(255, 140, 105)),
+    ("salmon2", RGB(238, 130, 98)),
+    ("salmon3", RGB(205, 112, 84)),
+    ("salmon4", RGB(139, 76, 57)),
+    ("sandybrown", RGB(244, 164, 96)),
+    ("seagreen", RGB(46, 139, 87)),
+    ("seagreen1", RGB(84, 255, 159)),
+    ("seagreen2", RGB(78, 238, 148)),
+    ("seagreen3", RGB(67, 205, 128)),
+    ("seagreen4", RGB(46, 139, 87)),
+    ("seashell", RGB(255, 245, 238)),
+    ("seashell1", RGB(255, 245, 238)),
+    ("seashell2", RGB(238, 229, 222)),
+    ("seashell3", RGB(205, 197, 191)),
+    ("seashell4", RGB(139, 134, 130)),
+    ("sienna", RGB(160, 82, 45)),
+    ("sienna1", RGB(255, 130, 71)),
+    ("sienna2", RGB(238, 121, 66)),
+    ("sienna3", RGB(205, 104, 57)),
+    ("sienna4", RGB(139, 71, 38)),
+    ("skyblue", RGB(135, 206, 235)),
+    ("skyblue1", RGB(135, 206, 255)),
+    ("skyblue2", RGB(126, 192, 238)),
+    ("skyblue3", RGB(108, 166, 205)),
+    ("skyblue4", RGB(74, 112, 139)),
+    ("slateblue", RGB(106, 90, 205)),
+    ("slateblue1", RGB(131, 111, 255)),
+    ("slateblue2", RGB(122, 103, 238)),
+    ("slateblue3", RGB(105, 89, 205)),
+    ("slateblue4", RGB(71, 60, 139)),
+    ("slategray", RGB(112, 128, 144)),
+    ("slategray1", RGB(198, 226, 255)),
+    ("slategray2", RGB(185, 211, 238)),
+    ("slategray3", RGB(159, 182, 205)),
+    ("slategray4", RGB(108, 123, 139)),
+    ("slategrey", RGB(112, 128, 144)),
+    ("snow", RGB(255, 250, 250)),
+    ("snow1", RGB(255, 250, 250)),
+    ("snow2", RGB(238, 233, 233)),
+    ("snow3", RGB(205, 201, 201)),
+    ("snow4", RGB(139, 137, 137)),
+    ("springgreen", RGB(0, 255, 127)),
+    ("springgreen1", RGB(0, 255, 127)),
+    ("springgreen2", RGB(0, 238, 118)),
+    ("springgreen3", RGB(0, 205, 102)),
+    ("springgreen4", RGB(0, 139, 69)),
+    ("steelblue", RGB(70, 130, 180)),
+    ("steelblue1", RGB(99, 184, 255)),
+    ("steelblue2", RGB(92, 172, 238)),
+    ("steelblue3", RGB(79, 148, 205)),
+    ("steelblue4", RGB(54, 100, 139)),
+    ("tan", RGB(210, 180, 140)),
+    ("tan1", RGB(255, 165, 79)),
+    ("tan2", RGB(238, 154, 73)),
+    ("tan3", RGB(205, 133, 63)),
+    ("tan4", RGB(139, 90, 43)),
+    ("thistle", RGB(216, 191, 216)),
+    ("thistle1", RGB(255, 225, 255)),
+    ("thistle2", RGB(238, 210, 238)),
+    ("thistle3", RGB(205, 181, 205)),
+    ("thistle4", RGB(139, 123, 139)),
+    ("tomato", RGB(255, 99, 71)),
+    ("tomato1", RGB(255, 99, 71)),
+    ("tomato2", RGB(238, 92, 66)),
+    ("tomato3", RGB(205, 79, 57)),
+    ("tomato4", RGB(139, 54, 38)),
+    ("turquoise", RGB(64, 224, 208)),
+    ("turquoise1", RGB(0, 245, 255)),
+    ("turquoise2", RGB(0, 229, 238)),
+    ("turquoise3", RGB(0, 197, 205)),
+    ("turquoise4", RGB(0, 134, 139)),
+    ("violet", RGB(238, 130, 238)),
+    ("violetred", RGB(208, 32, 144)),
+    ("violetred1", RGB(255, 62, 150)),
+    ("violetred2", RGB(238, 58, 140)),
+    ("violetred3", RGB(205, 50, 120)),
+    ("violetred4", RGB(139, 34, 82)),
+    ("wheat", RGB(245, 222, 179)),
+    ("wheat1", RGB(255, 231, 186)),
+    ("wheat2", RGB(238, 216, 174)),
+    ("wheat3", RGB(205, 186, 150)),
+    ("wheat4", RGB(139, 126, 102)),
+    ("white", RGB(255, 255, 255)),
+    ("whitesmoke", RGB(245, 245, 245)),
+    ("yellow", RGB(255, 255, 0)),
+    ("yellow1", RGB(255, 255, 0)),
+    ("yellow2", RGB(238, 238, 0)),
+    ("yellow3", RGB(205, 205, 0)),
+    ("yellow4", RGB(139, 139, 0)),
+    ("yellowgreen", RGB(154, 205, 50)),
+];
+
+/// Looks a named color up, in the CSS names first, then in the X11 names.
+pub fn lookup(name: &str) -> Option<RGB> {
+    let name = normalize(name);
+    find(CSS_COLORS, &name).or_else(|| find(X11_COLORS, &name))
+}
+
+/// Looks a CSS named color up.
+pub fn css(name: &str) -> Option<RGB> {
+    find(CSS_COLORS, &normalize(name))
+}
+
+/// Looks a X11 named color up.
+pub fn x11(name: &str) -> Option<RGB> {
+    find(X11_COLORS, &normalize(name))
+}
+
+/// Iterates over all names and the colors [`lookup`] returns for them,
+/// the CSS names first.
+pub fn names() -> impl Iterator<Item = (&'static str, RGB)> {
+    let x11 = X11_COLORS
+        .iter()
+        .filter(|(name, _)| find(CSS_COLORS, name).is_none());
+    CSS_COLORS.iter().chain(x11).copied()
+}
+
+/// Returns the name whose color is the closest to the given color, by the CIEDE2000
+/// color difference.
+pub fn nearest(color: RGB) -> (&'static str, RGB) {
+    let target = Lab::from(color);
+    names()
+        .map(|(name, candidate)| (name, candidate, target.delta_e_2000(&Lab::from(candidate))))
+        .fold(
+            None,
+            |nearest: Option<(&str, RGB, f64)>, current| match nearest {
+                Some(nearest) if nearest.2 <= current.2 => Some(nearest),
+                _ => Some(current),
+            },
+        )
+        .map(|(name, candidate, _)| (name, candidate))
+        .unwrap()
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|character| !character.is_whitespace() && *character != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn find(table: &[(&str, RGB)], name: &str) -> Option<RGB> {
+    table
+        .binary_search_by(|(candidate, _)| (*candidate).cmp(name))
+        .ok()
+        .map(|index| table[index].1)
+}
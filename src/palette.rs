@@ -0,0 +1,333 @@
+/*!
+# palette
+This module maps terminal colors to the RGB values a terminal displays for them.
+The 16 system colors differ between terminals, while the 6 × 6 × 6 cube and the
+grayscale ramp of the 256 color table are usually fixed, so a palette is built from
+its 16 system colors and can override any of the 256 entries afterwards. Presets
+hold the defaults of common terminals, and colors can be quantized to the nearest
+entry of a palette, as the terminal displaying them would show it.
+
+# Examples
+
+```
+use rustcolor::color::{Color, RGB};
+use rustcolor::palette::Palette;
+
+let palette = Palette::default();
+assert_eq!(Some(RGB(205, 0, 0)), palette.resolve(Color::C16(1)));
+assert_eq!(Some(RGB(255, 135, 0)), palette.resolve(Color::C256(208)));
+assert_eq!(None, palette.resolve(Color::Default));
+
+let vga = Palette::preset("vga").unwrap();
+assert_eq!(Some(RGB(170, 85, 0)), vga.resolve(Color::C16(3)));
+assert_eq!(Color::C16(3), vga.quantize_16(RGB(160, 90, 10)));
+assert_eq!(Color::C256(208), palette.quantize_256(RGB(250, 130, 10)));
+```
+*/
+
+use crate::color::{Color, RGB};
+use crate::space::Lab;
+use crate::style::TextStyle;
+use std::ops::Range;
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The names of the presets, as accepted by [`Palette::preset`].
+pub const PRESETS: [&str; 8] = [
+    "xterm",
+    "vga",
+    "windows10",
+    "solarized-dark",
+    "solarized-light",
+    "tango",
+    "macos",
+    "macos-dark",
+];
+
+/// The RGB values of the default foreground, default background and the 256 colors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    pub foreground: RGB,
+    pub background: RGB,
+    pub colors: [RGB; 256],
+}
+
+impl Palette {
+    /// Creates a palette from the default colors and the 16 system colors,
+    /// filling the remaining entries with the standard 256 color table.
+    pub fn new(foreground: RGB, background: RGB, system: [RGB; 16]) -> Self {
+        let mut colors = [RGB(0, 0, 0); 256];
+        colors[..16].copy_from_slice(&system);
+        for (index, color) in colors.iter_mut().enumerate().skip(16) {
+            *color = standard_color(index as u8);
+        }
+        Self {
+            foreground,
+            background,
+            colors,
+        }
+    }
+
+    /// The xterm default colors.
+    pub fn xterm() -> Self {
+        Palette::new(
+            RGB(229, 229, 229),
+            RGB(0, 0, 0),
+            [
+                RGB(0, 0, 0),
+                RGB(205, 0, 0),
+                RGB(0, 205, 0),
+                RGB(205, 205, 0),
+                RGB(0, 0, 238),
+                RGB(205, 0, 205),
+                RGB(0, 205, 205),
+                RGB(229, 229, 229),
+                RGB(127, 127, 127),
+                RGB(255, 0, 0),
+                RGB(0, 255, 0),
+                RGB(255, 255, 0),
+                RGB(92, 92, 255),
+                RGB(255, 0, 255),
+                RGB(0, 255, 255),
+                RGB(255, 255, 255),
+            ],
+        )
+    }
+
+    /// The colors of the VGA text mode, as used by the Linux console.
+    pub fn vga() -> Self {
+        Palette::new(
+            RGB(170, 170, 170),
+            RGB(0, 0, 0),
+            [
+                RGB(0, 0, 0),
+                RGB(170, 0, 0),
+                RGB(0, 170, 0),
+                RGB(170, 85, 0),
+                RGB(0, 0, 170),
+                RGB(170, 0, 170),
+                RGB(0, 170, 170),
+                RGB(170, 170, 170),
+                RGB(85, 85, 85),
+                RGB(255, 85, 85),
+                RGB(85, 255, 85),
+                RGB(255, 255, 85),
+                RGB(85, 85, 255),
+                RGB(255, 85, 255),
+                RGB(85, 255, 255),
+                RGB(255, 255, 255),
+            ],
+        )
+    }
+
+    /// The Campbell scheme of the Windows 10 console.
+    pub fn windows10() -> Self {
+        Palette::new(
+            RGB(204, 204, 204),
+            RGB(12, 12, 12),
+            [
+                RGB(12, 12, 12),
+                RGB(197, 15, 31),
+                RGB(19, 161, 14),
+                RGB(193, 156, 0),
+                RGB(0, 55, 218),
+                RGB(136, 23, 152),
+                RGB(58, 150, 221),
+                RGB(204, 204, 204),
+                RGB(118, 118, 118),
+                RGB(231, 72, 86),
+                RGB(22, 198, 12),
+                RGB(249, 241, 165),
+                RGB(59, 120, 255),
+                RGB(180, 0, 158),
+                RGB(97, 214, 214),
+                RGB(242, 242, 242),
+            ],
+        )
+    }
+
+    /// The dark Solarized scheme.
+    pub fn solarized_dark() -> Self {
+        Palette::new(RGB(131, 148, 150), RGB(0, 43, 54), SOLARIZED)
+    }
+
+    /// The light Solarized scheme.
+    pub fn solarized_light() -> Self {
+        Palette::new(RGB(101, 123, 131), RGB(253, 246, 227), SOLARIZED)
+    }
+
+    /// The Tango scheme of GNOME Terminal.
+    pub fn tango() -> Self {
+        Palette::new(
+            RGB(211, 215, 207),
+            RGB(46, 52, 54),
+            [
+                RGB(46, 52, 54),
+                RGB(204, 0, 0),
+                RGB(78, 154, 6),
+                RGB(196, 160, 0),
+                RGB(52, 101, 164),
+                RGB(117, 80, 123),
+                RGB(6, 152, 154),
+                RGB(211, 215, 207),
+                RGB(85, 87, 83),
+                RGB(239, 41, 41),
+                RGB(138, 226, 52),
+                RGB(252, 233, 79),
+                RGB(114, 159, 207),
+                RGB(173, 127, 168),
+                RGB(52, 226, 226),
+                RGB(238, 238, 236),
+            ],
+        )
+    }
+
+    /// The Basic profile of the macOS Terminal, black on white.
+    pub fn macos() -> Self {
+        Palette::new(RGB(0, 0, 0), RGB(255, 255, 255), MACOS)
+    }
+
+    /// The macOS Terminal colors, white on black.
+    pub fn macos_dark() -> Self {
+        Palette::new(RGB(255, 255, 255), RGB(0, 0, 0), MACOS)
+    }
+
+    /// Returns a preset by its name, one of the [`PRESETS`].
+    pub fn preset(name: &str) -> Option<Palette> {
+        match name {
+            "xterm" => Some(Palette::xterm()),
+            "vga" => Some(Palette::vga()),
+            "windows10" => Some(Palette::windows10()),
+            "solarized-dark" => Some(Palette::solarized_dark()),
+            "solarized-light" => Some(Palette::solarized_light()),
+            "tango" => Some(Palette::tango()),
+            "macos" => Some(Palette::macos()),
+            "macos-dark" => Some(Palette::macos_dark()),
+            _ => None,
+        }
+    }
+
+    /// Resolves a color to RGB, or `None` for the terminal default color.
+    pub fn resolve(&self, color: Color) -> Option<RGB> {
+        match color {
+            Color::Default => None,
+            Color::C16(n) => Some(self.colors[(n & 15) as usize]),
+            Color::C256(n) => Some(self.colors[n as usize]),
+            Color::Rgb(rgb) => Some(rgb),
+        }
+    }
+
+    /// Resolves a foreground color, unset and default colors being the default foreground.
+    pub fn resolve_foreground(&self, color: Option<Color>) -> RGB {
+        color
+            .and_then(|color| self.resolve(color))
+            .unwrap_or(self.foreground)
+    }
+
+    /// Resolves a background color, unset and default colors being the default background.
+    pub fn resolve_background(&self, color: Option<Color>) -> RGB {
+        color
+            .and_then(|color| self.resolve(color))
+            .unwrap_or(self.background)
+    }
+
+    /// Resolves the displayed foreground and background of a style, swapped by reverse video.
+    pub fn resolve_style(&self, style: &TextStyle) -> (RGB, RGB) {
+        let foreground = self.resolve_foreground(style.foreground);
+        let background = self.resolve_background(style.background);
+        if style.reverse {
+            (background, foreground)
+        } else {
+            (foreground, background)
+        }
+    }
+
+    /// Returns the entries 16 to 255 which differ from the standard 256 color table.
+    pub fn overrides(&self) -> impl Iterator<Item = (u8, RGB)> + '_ {
+        (16..=255)
+            .map(move |index| (index, self.colors[index as usize]))
+            .filter(|(index, color)| *color != standard_color(*index))
+    }
+
+    /// Returns the system color displayed the closest to a color, by CIEDE2000 distance.
+    pub fn quantize_16(&self, color: RGB) -> Color {
+        Color::C16(self.nearest(color, 0..16))
+    }
+
+    /// Returns the entry of the color cube or grayscale ramp displayed the closest to a
+    /// color, by CIEDE2000 distance. The system colors 0 to 15 are left out, as users
+    /// and themes reconfigure them.
+    pub fn quantize_256(&self, color: RGB) -> Color {
+        Color::C256(self.nearest(color, 16..256))
+    }
+
+    fn nearest(&self, color: RGB, entries: Range<usize>) -> u8 {
+        let lab = Lab::from(color);
+        let mut nearest = (entries.start, f64::INFINITY);
+        for index in entries {
+            let distance = lab.delta_e_2000(&Lab::from(self.colors[index]));
+            if distance < nearest.1 {
+                nearest = (index, distance);
+            }
+        }
+        nearest.0 as u8
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::xterm()
+    }
+}
+
+const SOLARIZED: [RGB; 16] = [
+    RGB(7, 54, 66),
+    RGB(220, 50, 47),
+    RGB(133, 153, 0),
+    RGB(181, 137, 0),
+    RGB(38, 139, 210),
+    RGB(211, 54, 130),
+    RGB(42, 161, 152),
+    RGB(238, 232, 213),
+    RGB(0, 43, 54),
+    RGB(203, 75, 22),
+    RGB(88, 110, 117),
+    RGB(101, 123, 131),
+    RGB(131, 148, 150),
+    RGB(108, 113, 196),
+    RGB(147, 161, 161),
+    RGB(253, 246, 227),
+];
+
+const MACOS: [RGB; 16] = [
+    RGB(0, 0, 0),
+    RGB(194, 54, 33),
+    RGB(37, 188, 36),
+    RGB(173, 173, 39),
+    RGB(73, 46, 225),
+    RGB(211, 56, 211),
+    RGB(51, 187, 200),
+    RGB(203, 204, 205),
+    RGB(129, 131, 131),
+    RGB(252, 57, 31),
+    RGB(49, 231, 34),
+    RGB(234, 236, 35),
+    RGB(88, 51, 255),
+    RGB(249, 53, 248),
+    RGB(20, 240, 240),
+    RGB(233, 235, 235),
+];
+
+/// Returns the standard RGB value of the 256 color table entries 16 to 255.
+fn standard_color(index: u8) -> RGB {
+    if index >= 232 {
+        let level = 8 + 10 * (index - 232);
+        return RGB(level, level, level);
+    }
+    let cube = index - 16;
+    RGB(
+        CUBE_LEVELS[(cube / 36) as usize],
+        CUBE_LEVELS[(cube / 6 % 6) as usize],
+        CUBE_LEVELS[(cube % 6) as usize],
+    )
+}
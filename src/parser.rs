@@ -0,0 +1,515 @@
+/*!
+# parser
+This module implements a streaming parser for terminal output, following the
+standard VT state machine described by [Paul Williams](https://vt100.net/emu/dec_ansi_parser).
+The byte input is tokenized into text runs, control characters, SGR changes
+(decoded into the [`TextStyle`](crate::style::TextStyle) model), other control sequences,
+operating system commands, device control strings and escape sequences.
+
+Malformed input never fails: invalid UTF-8 is replaced by U+FFFD, broken sequences
+are dropped and a sequence interrupted by a new ESC is abandoned.
+
+[`strip`] removes all the sequences, styles and hyperlinks alike, and [`width`]
+measures the columns the remaining text takes in a terminal.
+
+# Examples
+
+```
+use rustcolor::color::Color;
+use rustcolor::parser::{parse, Token};
+use rustcolor::style::Sgr;
+
+let tokens = parse("\u{001b}[31mred\u{001b}[0m");
+assert_eq!(
+    vec![
+        Token::Sgr(vec![Sgr::Foreground(Color::C16(1))]),
+        Token::Text("red".to_owned()),
+        Token::Sgr(vec![Sgr::Reset]),
+    ],
+    tokens
+);
+```
+*/
+
+use crate::style::Sgr;
+
+const MAX_PARAMS: usize = 32;
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+const CAN: u8 = 0x18;
+const SUB: u8 = 0x1a;
+const DEL: u8 = 0x7f;
+const ST_FINAL: u8 = b'\\';
+
+/// A token read from terminal output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    /// A run of printable text.
+    Text(String),
+    /// A C0 control character, such as line feed, carriage return or backspace.
+    Control(u8),
+    /// A select graphic rendition sequence, **ESC[...m**, decoded into style changes.
+    Sgr(Vec<Sgr>),
+    /// Any other control sequence, **ESC[...**. Private markers are kept in intermediates.
+    Csi {
+        params: Vec<Vec<u16>>,
+        intermediates: Vec<u8>,
+        action: char,
+    },
+    /// An operating system command, **ESC]...**, with its payload converted lossily to UTF-8.
+    Osc(String),
+    /// A device control string, **ESCP...**, with its passthrough data.
+    Dcs {
+        params: Vec<Vec<u16>>,
+        intermediates: Vec<u8>,
+        action: char,
+        data: Vec<u8>,
+    },
+    /// An escape sequence that does not introduce a control sequence or string.
+    Esc {
+        intermediates: Vec<u8>,
+        action: char,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Ground,
+    Escape,
+    EscapeIntermediate,
+    CsiEntry,
+    CsiParam,
+    CsiIntermediate,
+    CsiIgnore,
+    DcsEntry,
+    DcsParam,
+    DcsIntermediate,
+    DcsPassthrough,
+    DcsIgnore,
+    OscString,
+    SosPmApcString,
+}
+
+/// A streaming tokenizer for terminal output.
+///
+/// Bytes may be fed in chunks of any size, sequences and UTF-8 characters split
+/// between chunks are handled.
+pub struct Parser {
+    state: State,
+    text: String,
+    utf8: Vec<u8>,
+    params: Vec<Vec<u16>>,
+    param: Vec<u16>,
+    value: u16,
+    has_params: bool,
+    intermediates: Vec<u8>,
+    action: char,
+    data: Vec<u8>,
+    terminated_string: bool,
+    tokens: Vec<Token>,
+}
+
+impl Parser {
+    /// Creates a new parser in the ground state.
+    pub fn new() -> Self {
+        Self {
+            state: State::Ground,
+            text: String::new(),
+            utf8: Vec::new(),
+            params: Vec::new(),
+            param: Vec::new(),
+            value: 0,
+            has_params: false,
+            intermediates: Vec::new(),
+            action: '\0',
+            data: Vec::new(),
+            terminated_string: false,
+            tokens: Vec::new(),
+        }
+    }
+
+    /// Feeds bytes to the parser, returning all tokens completed by them.
+    /// Pending text is flushed at the end of each call.
+    pub fn advance(&mut self, bytes: &[u8]) -> Vec<Token> {
+        for &byte in bytes {
+            self.byte(byte);
+        }
+        self.flush_text();
+        std::mem::take(&mut self.tokens)
+    }
+
+    /// Ends the input, flushing an incomplete UTF-8 character as U+FFFD and
+    /// dropping any truncated sequence.
+    pub fn finish(&mut self) -> Vec<Token> {
+        if !self.utf8.is_empty() {
+            self.utf8.clear();
+            self.text.push(char::REPLACEMENT_CHARACTER);
+        }
+        self.flush_text();
+        self.state = State::Ground;
+        std::mem::take(&mut self.tokens)
+    }
+
+    fn byte(&mut self, byte: u8) {
+        if !self.utf8.is_empty() && (byte < 0x80 || self.state != State::Ground) {
+            self.utf8.clear();
+            self.text.push(char::REPLACEMENT_CHARACTER);
+        }
+
+        match byte {
+            CAN | SUB => {
+                self.execute(byte);
+                self.state = State::Ground;
+                return;
+            }
+            ESC => {
+                self.terminated_string = match self.state {
+                    State::OscString => {
+                        self.dispatch_osc();
+                        true
+                    }
+                    State::DcsPassthrough => {
+                        self.dispatch_dcs();
+                        true
+                    }
+                    State::DcsIgnore | State::SosPmApcString => true,
+                    _ => false,
+                };
+                self.clear();
+                self.state = State::Escape;
+                return;
+            }
+            _ => {}
+        }
+
+        match self.state {
+            State::Ground => match byte {
+                0x00..=0x1f => self.execute(byte),
+                DEL => {}
+                0x20..=0x7e => self.text.push(byte as char),
+                _ => self.print_utf8(byte),
+            },
+            State::Escape => match byte {
+                0x00..=0x1f => self.execute(byte),
+                0x20..=0x2f => {
+                    self.intermediates.push(byte);
+                    self.state = State::EscapeIntermediate;
+                }
+                b'[' => self.state = State::CsiEntry,
+                b']' => self.state = State::OscString,
+                b'P' => self.state = State::DcsEntry,
+                b'X' | b'^' | b'_' => self.state = State::SosPmApcString,
+                ST_FINAL if self.terminated_string => self.state = State::Ground,
+                0x30..=0x7e => self.dispatch_esc(byte),
+                _ => {}
+            },
+            State::EscapeIntermediate => match byte {
+                0x00..=0x1f => self.execute(byte),
+                0x20..=0x2f => self.intermediates.push(byte),
+                0x30..=0x7e => self.dispatch_esc(byte),
+                _ => {}
+            },
+            State::CsiEntry | State::CsiParam => match byte {
+                0x00..=0x1f => self.execute(byte),
+                0x30..=0x3b => {
+                    self.param_byte(byte);
+                    self.state = State::CsiParam;
+                }
+                0x3c..=0x3f if self.state == State::CsiEntry => {
+                    self.intermediates.push(byte);
+                    self.state = State::CsiParam;
+                }
+                0x3c..=0x3f => self.state = State::CsiIgnore,
+                0x20..=0x2f => {
+                    self.intermediates.push(byte);
+                    self.state = State::CsiIntermediate;
+                }
+                0x40..=0x7e => self.dispatch_csi(byte),
+                _ => {}
+            },
+            State::CsiIntermediate => match byte {
+                0x00..=0x1f => self.execute(byte),
+                0x20..=0x2f => self.intermediates.push(byte),
+                0x30..=0x3f => self.state = State::CsiIgnore,
+                0x40..=0x7e => self.dispatch_csi(byte),
+                _ => {}
+            },
+            State::CsiIgnore => match byte {
+                0x00..=0x1f => self.execute(byte),
+                0x40..=0x7e => self.state = State::Ground,
+                _ => {}
+            },
+            State::DcsEntry | State::DcsParam => match byte {
+                0x30..=0x3b => {
+                    self.param_byte(byte);
+                    self.state = State::DcsParam;
+                }
+                0x3c..=0x3f if self.state == State::DcsEntry => {
+                    self.intermediates.push(byte);
+                    self.state = State::DcsParam;
+                }
+                0x3c..=0x3f => self.state = State::DcsIgnore,
+                0x20..=0x2f => {
+                    self.intermediates.push(byte);
+                    self.state = State::DcsIntermediate;
+                }
+                0x40..=0x7e => self.hook(byte),
+                _ => {}
+            },
+            State::DcsIntermediate => match byte {
+                0x20..=0x2f => self.intermediates.push(byte),
+                0x30..=0x3f => self.state = State::DcsIgnore,
+                0x40..=0x7e => self.hook(byte),
+                _ => {}
+            },
+            State::DcsPassthrough => match byte {
+                DEL => {}
+                _ => self.data.push(byte),
+            },
+            State::OscString => match byte {
+                BEL => {
+                    self.dispatch_osc();
+                    self.state = State::Ground;
+                }
+                0x00..=0x1f => {}
+                _ => self.data.push(byte),
+            },
+            State::DcsIgnore | State::SosPmApcString => {}
+        }
+    }
+
+    fn print_utf8(&mut self, byte: u8) {
+        if self.utf8.is_empty() {
+            match byte {
+                0xc2..=0xf4 => self.utf8.push(byte),
+                _ => self.text.push(char::REPLACEMENT_CHARACTER),
+            }
+            return;
+        }
+
+        if byte & 0xc0 != 0x80 {
+            self.utf8.clear();
+            self.text.push(char::REPLACEMENT_CHARACTER);
+            self.print_utf8(byte);
+            return;
+        }
+
+        self.utf8.push(byte);
+        let needed = match self.utf8[0] {
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            _ => 4,
+        };
+        if self.utf8.len() == needed {
+            match std::str::from_utf8(&self.utf8) {
+                Ok(character) => self.text.push_str(character),
+                Err(_) => self.text.push(char::REPLACEMENT_CHARACTER),
+            }
+            self.utf8.clear();
+        }
+    }
+
+    fn param_byte(&mut self, byte: u8) {
+        self.has_params = true;
+        match byte {
+            b';' => {
+                self.param.push(self.value);
+                let param = std::mem::take(&mut self.param);
+                if self.params.len() < MAX_PARAMS {
+                    self.params.push(param);
+                }
+                self.value = 0;
+            }
+            b':' => {
+                self.param.push(self.value);
+                self.value = 0;
+            }
+            _ => {
+                self.value = self
+                    .value
+                    .saturating_mul(10)
+                    .saturating_add((byte - b'0') as u16);
+            }
+        }
+    }
+
+    fn take_params(&mut self) -> Vec<Vec<u16>> {
+        if self.has_params {
+            self.param.push(self.value);
+            let param = std::mem::take(&mut self.param);
+            if self.params.len() < MAX_PARAMS {
+                self.params.push(param);
+            }
+        }
+        std::mem::take(&mut self.params)
+    }
+
+    fn execute(&mut self, byte: u8) {
+        self.flush_text();
+        self.tokens.push(Token::Control(byte));
+    }
+
+    fn dispatch_esc(&mut self, byte: u8) {
+        self.flush_text();
+        self.tokens.push(Token::Esc {
+            intermediates: std::mem::take(&mut self.intermediates),
+            action: byte as char,
+        });
+        self.state = State::Ground;
+    }
+
+    fn dispatch_csi(&mut self, byte: u8) {
+        self.flush_text();
+        let params = self.take_params();
+        let intermediates = std::mem::take(&mut self.intermediates);
+        let token = if byte == b'm' && intermediates.is_empty() {
+            Token::Sgr(Sgr::decode(&params))
+        } else {
+            Token::Csi {
+                params,
+                intermediates,
+                action: byte as char,
+            }
+        };
+        self.tokens.push(token);
+        self.state = State::Ground;
+    }
+
+    fn hook(&mut self, byte: u8) {
+        self.action = byte as char;
+        self.state = State::DcsPassthrough;
+    }
+
+    fn dispatch_dcs(&mut self) {
+        self.flush_text();
+        let params = self.take_params();
+        self.tokens.push(Token::Dcs {
+            params,
+            intermediates: std::mem::take(&mut self.intermediates),
+            action: self.action,
+            data: std::mem::take(&mut self.data),
+        });
+    }
+
+    fn dispatch_osc(&mut self) {
+        self.flush_text();
+        let data = std::mem::take(&mut self.data);
+        self.tokens
+            .push(Token::Osc(String::from_utf8_lossy(&data).into_owned()));
+    }
+
+    fn flush_text(&mut self) {
+        if !self.text.is_empty() {
+            self.tokens
+                .push(Token::Text(std::mem::take(&mut self.text)));
+        }
+    }
+
+    fn clear(&mut self) {
+        self.params.clear();
+        self.param.clear();
+        self.value = 0;
+        self.has_params = false;
+        self.intermediates.clear();
+        self.data.clear();
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tokenizes a complete terminal output.
+pub fn parse(input: &str) -> Vec<Token> {
+    let mut parser = Parser::new();
+    let mut tokens = parser.advance(input.as_bytes());
+    tokens.extend(parser.finish());
+    tokens
+}
+
+/// Removes the escape sequences of a terminal output, keeping its text and its
+/// control characters, such as line feeds and tabs.
+pub fn strip(input: &str) -> String {
+    let mut text = String::with_capacity(input.len());
+    for token in parse(input) {
+        match token {
+            Token::Text(run) => text.push_str(&run),
+            Token::Control(byte) => text.push(byte as char),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Returns the columns the widest line of a terminal output takes. Escape sequences,
+/// control characters and combining marks take none, East Asian wide characters and
+/// emoji take two.
+pub fn width(input: &str) -> usize {
+    let mut widest = 0;
+    let mut line = 0;
+    for token in parse(input) {
+        match token {
+            Token::Text(run) => line += run.chars().map(char_width).sum::<usize>(),
+            Token::Control(b'\n') | Token::Control(b'\r') => {
+                widest = widest.max(line);
+                line = 0;
+            }
+            _ => {}
+        }
+    }
+    widest.max(line)
+}
+
+/// Returns the columns a printable character takes.
+fn char_width(character: char) -> usize {
+    let code = character as u32;
+    let zero = [
+        (0x0300, 0x036f),
+        (0x0483, 0x0489),
+        (0x0591, 0x05bd),
+        (0x0610, 0x061a),
+        (0x064b, 0x065f),
+        (0x1ab0, 0x1aff),
+        (0x1dc0, 0x1dff),
+        (0x200b, 0x200f),
+        (0x20d0, 0x20ff),
+        (0xfe00, 0xfe0f),
+        (0xfe20, 0xfe2f),
+        (0xe0100, 0xe01ef),
+    ];
+    let wide = [
+        (0x1100, 0x115f),
+        (0x231a, 0x231b),
+        (0x2329, 0x232a),
+        (0x23e9, 0x23ec),
+        (0x25fd, 0x25fe),
+        (0x2614, 0x2615),
+        (0x2e80, 0x303e),
+        (0x3041, 0x33ff),
+        (0x3400, 0x4dbf),
+        (0x4e00, 0x9fff),
+        (0xa000, 0xa4cf),
+        (0xac00, 0xd7a3),
+        (0xf900, 0xfaff),
+        (0xfe30, 0xfe4f),
+        (0xff00, 0xff60),
+        (0xffe0, 0xffe6),
+        (0x1f300, 0x1f64f),
+        (0x1f900, 0x1f9ff),
+        (0x20000, 0x3fffd),
+    ];
+    let within = |ranges: &[(u32, u32)]| {
+        ranges
+            .iter()
+            .any(|(first, last)| code >= *first && code <= *last)
+    };
+    if within(&zero) {
+        0
+    } else if within(&wide) {
+        2
+    } else {
+        1
+    }
+}
@@ -2,15 +2,19 @@
 # printer
 This module implements all functionalities to print fomatted ansi scape text, as
 predefined styles. Thre trait ColorPrinter enhances the String type, adding new functions in it.
+The predefined styles are rendered through the active [`Theme`](crate::theme::Theme).
  */
 
-use std::usize;
-
 use crate::color::*;
+use crate::hyperlink::Hyperlink;
 use crate::style::StyleBuilder;
+use crate::theme::{self, Role};
 
 /// ColorPrinter is a trait thats enhances String data type with print_c16 and print_c256 functions.
 /// function.
+///
+/// The role and hyperlink functions have default implementations for the types
+/// viewed as text, so implementors only provide the original functions.
 pub trait ColorPrinter {
     /// Enhance the given string with 16 color ansi scaped sequence.
     ///
@@ -47,7 +51,7 @@ pub trait ColorPrinter {
 
     fn print_24bit(&self, foreground: RGB, background: RGB) -> String;
 
-    /// Enhance the given string with a yellow fg, default bg color text.
+    /// Enhance the given string with the warn style, a yellow fg, default bg color text by default.
     ///
     /// # Examples
     ///
@@ -58,7 +62,7 @@ pub trait ColorPrinter {
     /// ```
     fn warn(&self) -> String;
 
-    /// Enhance the given string with a white fg, red bg color text.
+    /// Enhance the given string with the error style, a white fg, red bg color text by default.
     ///
     /// # Examples
     ///
@@ -69,7 +73,7 @@ pub trait ColorPrinter {
     /// ```
     fn error(&self) -> String;
 
-    /// Enhance the given string with a red fg, default bg color text.
+    /// Enhance the given string with the danger style, a red fg, default bg color text by default.
     ///
     /// # Examples
     ///
@@ -80,7 +84,7 @@ pub trait ColorPrinter {
     /// ```
     fn danger(&self) -> String;
 
-    /// Enhance the given string with a green fg, default bg color text.
+    /// Enhance the given string with the info style, a green fg, default bg color text by default.
     ///
     /// # Examples
     ///
@@ -91,7 +95,7 @@ pub trait ColorPrinter {
     /// ```
     fn info(&self) -> String;
 
-    /// Enhance the given string with a blue fg, default bg color text.
+    /// Enhance the given string with the primary style, a blue fg, default bg color text by default.
     ///
     /// # Examples
     ///
@@ -102,7 +106,7 @@ pub trait ColorPrinter {
     /// ```
     fn primary(&self) -> String;
 
-    /// Enhance the given string with a red fg, blink tag and default bg color text.
+    /// Enhance the given string with the blink style, a red fg, blink tag and default bg color text by default.
     ///
     /// # Examples
     ///
@@ -113,7 +117,7 @@ pub trait ColorPrinter {
     /// ```
     fn blink(&self) -> String;
 
-    /// Enhance the given string with a yellow fg, underline tag and default bg color text.
+    /// Enhance the given string with the underline style, a yellow fg, underline tag and default bg color text by default.
     ///
     /// # Examples
     ///
@@ -123,6 +127,120 @@ pub trait ColorPrinter {
     /// println!("{}", "this is the underlined style".underline());
     /// ```
     fn underline(&self) -> String;
+
+    /// Enhance the given string with the success style, a bold green fg by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::printer::*;
+    ///
+    /// println!("{}", "this is the success style".success());
+    /// ```
+    fn success(&self) -> String
+    where
+        Self: AsRef<str>,
+    {
+        self.role(Role::Success)
+    }
+
+    /// Enhance the given string with the muted style, a gray fg by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::printer::*;
+    ///
+    /// println!("{}", "this is the muted style".muted());
+    /// ```
+    fn muted(&self) -> String
+    where
+        Self: AsRef<str>,
+    {
+        self.role(Role::Muted)
+    }
+
+    /// Enhance the given string with the emphasis style, bold by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::printer::*;
+    ///
+    /// println!("{}", "this is the emphasis style".emphasis());
+    /// ```
+    fn emphasis(&self) -> String
+    where
+        Self: AsRef<str>,
+    {
+        self.role(Role::Emphasis)
+    }
+
+    /// Enhance the given string with the code style, a cyan fg by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::printer::*;
+    ///
+    /// println!("{}", "this is the code style".code());
+    /// ```
+    fn code(&self) -> String
+    where
+        Self: AsRef<str>,
+    {
+        self.role(Role::Code)
+    }
+
+    /// Enhance the given string with the link style, an underlined blue fg by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::printer::*;
+    ///
+    /// println!("{}", "this is the link style".link());
+    /// ```
+    fn link(&self) -> String
+    where
+        Self: AsRef<str>,
+    {
+        self.role(Role::Link)
+    }
+
+    /// Enhance the given string with the style of any role of the active theme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::printer::*;
+    /// use rustcolor::theme::Role;
+    ///
+    /// assert_eq!("this is the info style".info(), "this is the info style".role(Role::Info));
+    /// ```
+    fn role(&self, role: Role) -> String
+    where
+        Self: AsRef<str>,
+    {
+        theme::active(|theme| theme.paint(role, self.as_ref()))
+    }
+
+    /// Makes the given string, styled or not, a link to an URL, in terminals supporting
+    /// OSC 8 hyperlinks. The string is left unchanged elsewhere.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::printer::*;
+    ///
+    /// println!("{}", "src/lib.rs".code().hyperlink("file:///src/lib.rs"));
+    /// ```
+    fn hyperlink(&self, url: &str) -> String
+    where
+        Self: AsRef<str>,
+    {
+        Hyperlink::new(url).render(self.as_ref())
+    }
 }
 
 impl ColorPrinter for str {
@@ -163,60 +281,31 @@ impl ColorPrinter for str {
     }
 
     fn error(&self) -> String {
-        let result = self.print_c16(FG_WHITE, BG_RED);
-        result
+        self.role(Role::Error)
     }
 
     fn danger(&self) -> String {
-        let result = self.print_c16(FG_RED, BG_DEFAULT);
-        result
+        self.role(Role::Danger)
     }
 
     fn info(&self) -> String {
-        let result = self.print_c16(FG_GREEN, BG_DEFAULT);
-        result
+        self.role(Role::Info)
     }
 
     fn primary(&self) -> String {
-        let result = self.print_c16(FG_BLUE, BG_DEFAULT);
-        result
+        self.role(Role::Primary)
     }
 
     fn warn(&self) -> String {
-        let result = self.print_c16(FG_YELLOW, BG_DEFAULT);
-        result
+        self.role(Role::Warn)
     }
 
     fn blink(&self) -> String {
-        let result = StyleBuilder::new()
-            .csi()
-            .color(FG_RED)
-            .delimiter()
-            .blink()
-            .end_sgr()
-            .message()
-            .csi()
-            .reset()
-            .end_sgr()
-            .build();
-
-        result.render(self)
+        self.role(Role::Blink)
     }
 
     fn underline(&self) -> String {
-        let result = StyleBuilder::new()
-            .csi()
-            .color(FG_YELLOW)
-            .delimiter()
-            .underline()
-            .end_sgr()
-            .message()
-            .csi()
-            .reset()
-            .end_sgr()
-            .build();
-
-        result.render(self)
+        self.role(Role::Underline)
     }
 
     fn print_24bit(&self, foreground: RGB, background: RGB) -> String {
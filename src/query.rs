@@ -0,0 +1,270 @@
+/*!
+# query
+This module asks the terminal for the colors it actually displays. The OSC 10 and 11
+queries return the default foreground and background, and OSC 4 returns an entry of
+the 256 color table, as `rgb:RRRR/GGGG/BBBB` replies. Each batch of queries is followed
+by a primary device attributes request, which every terminal answers, so a terminal
+ignoring color queries is detected without waiting for the whole timeout.
+
+The queries go through any [`Write`] and the replies are read from any [`ReadTimeout`],
+so they can be tested against a scripted terminal. On unix, [`Tty`] talks to the
+controlling terminal.
+
+# Examples
+
+```
+use rustcolor::color::RGB;
+use rustcolor::query::Query;
+
+// a terminal replying with a white background
+let replies: &[u8] = b"\x1b]11;rgb:ffff/ffff/ffff\x1b\\\x1b[?62;22c";
+let mut sent = Vec::new();
+let mut query = Query::new(replies, &mut sent);
+
+assert_eq!(RGB(255, 255, 255), query.background().unwrap());
+assert_eq!(b"\x1b]11;?\x07\x1b[c".to_vec(), sent);
+```
+*/
+
+use crate::color::RGB;
+use crate::palette::Palette;
+use crate::parser::{Parser, Token};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+/// The time to wait for the replies of a batch of queries, by default.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(100);
+/// The primary device attributes request, answered by all terminals.
+const DEVICE_ATTRIBUTES: &str = "\u{001b}[c";
+
+/// A source of terminal replies which gives up waiting after a timeout.
+pub trait ReadTimeout {
+    /// Reads the available bytes, waiting at most `timeout` for some to arrive.
+    /// Returns `Ok(0)` if none arrived in time.
+    fn read_timeout(&mut self, buffer: &mut [u8], timeout: Duration) -> io::Result<usize>;
+}
+
+/// Scripted replies, all available at once.
+impl ReadTimeout for &[u8] {
+    fn read_timeout(&mut self, buffer: &mut [u8], _timeout: Duration) -> io::Result<usize> {
+        self.read(buffer)
+    }
+}
+
+/// The reasons a query can fail.
+#[derive(Debug)]
+pub enum QueryError {
+    /// Reading or writing the terminal failed.
+    Io(io::Error),
+    /// The terminal did not answer in time.
+    Timeout,
+    /// The terminal answered the device attributes request but not the color query.
+    Unsupported,
+    /// The terminal answered with a color that could not be parsed.
+    InvalidReply(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::Io(error) => write!(f, "terminal i/o failed: {}", error),
+            QueryError::Timeout => write!(f, "the terminal did not answer in time"),
+            QueryError::Unsupported => write!(f, "the terminal does not support color queries"),
+            QueryError::InvalidReply(reply) => write!(f, "invalid color reply: {:?}", reply),
+        }
+    }
+}
+
+impl Error for QueryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QueryError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for QueryError {
+    fn from(error: io::Error) -> Self {
+        QueryError::Io(error)
+    }
+}
+
+/// Sends color queries to a terminal and reads its replies.
+pub struct Query<R, W> {
+    reader: R,
+    writer: W,
+    timeout: Duration,
+}
+
+impl<R: ReadTimeout, W: Write> Query<R, W> {
+    /// Creates a query reading the replies from `reader` and writing the queries to `writer`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader,
+            writer,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the time to wait for the replies of a batch of queries.
+    pub fn timeout(mut self, timeout: Duration) -> Query<R, W> {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the default foreground color, with OSC 10.
+    pub fn foreground(&mut self) -> Result<RGB, QueryError> {
+        self.single("10")
+    }
+
+    /// Returns the default background color, with OSC 11.
+    pub fn background(&mut self) -> Result<RGB, QueryError> {
+        self.single("11")
+    }
+
+    /// Returns an entry of the 256 color table, with OSC 4.
+    pub fn color(&mut self, index: u8) -> Result<RGB, QueryError> {
+        self.single(&format!("4;{}", index))
+    }
+
+    /// Returns a palette of the default colors and the 16 system colors, in a single
+    /// batch of queries. The rest of the 256 color table keeps its standard values.
+    pub fn palette(&mut self) -> Result<Palette, QueryError> {
+        let mut codes = vec!["10".to_owned(), "11".to_owned()];
+        codes.extend((0..16).map(|index| format!("4;{}", index)));
+        let colors = self.batch(&codes)?;
+        let mut system = [RGB(0, 0, 0); 16];
+        system.copy_from_slice(&colors[2..]);
+        Ok(Palette::new(colors[0], colors[1], system))
+    }
+
+    fn single(&mut self, code: &str) -> Result<RGB, QueryError> {
+        self.batch(&[code.to_owned()]).map(|colors| colors[0])
+    }
+
+    /// Sends the queries followed by a device attributes request, and returns the
+    /// colors in the order of the codes once they all arrived.
+    fn batch(&mut self, codes: &[String]) -> Result<Vec<RGB>, QueryError> {
+        let mut request = String::new();
+        for code in codes {
+            request.push_str(&format!("\u{001b}]{};?\u{0007}", code));
+        }
+        request.push_str(DEVICE_ATTRIBUTES);
+        self.writer.write_all(request.as_bytes())?;
+        self.writer.flush()?;
+
+        let mut colors: Vec<Option<RGB>> = vec![None; codes.len()];
+        let mut answered = false;
+        let mut parser = Parser::new();
+        let mut buffer = [0; 256];
+        let deadline = Instant::now() + self.timeout;
+        while !answered {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            let read = self.reader.read_timeout(&mut buffer, remaining)?;
+            if read == 0 {
+                break;
+            }
+            for token in parser.advance(&buffer[..read]) {
+                match token {
+                    Token::Osc(payload) => {
+                        let found = codes.iter().position(|code| {
+                            payload.starts_with(code.as_str())
+                                && payload[code.len()..].starts_with(';')
+                        });
+                        if let Some(index) = found {
+                            let spec = &payload[codes[index].len() + 1..];
+                            let color = spec
+                                .parse()
+                                .map_err(|_| QueryError::InvalidReply(payload.clone()))?;
+                            colors[index] = Some(color);
+                        }
+                    }
+                    Token::Csi { action: 'c', .. } => answered = true,
+                    _ => {}
+                }
+            }
+        }
+        match colors.into_iter().collect::<Option<Vec<RGB>>>() {
+            Some(colors) => Ok(colors),
+            None if answered => Err(QueryError::Unsupported),
+            None => Err(QueryError::Timeout),
+        }
+    }
+}
+
+#[cfg(unix)]
+pub use self::tty::Tty;
+
+#[cfg(unix)]
+mod tty {
+    use super::{Query, ReadTimeout};
+    use std::fs::{File, OpenOptions};
+    use std::io::{self, Read};
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    /// The controlling terminal, switched to raw mode until dropped.
+    ///
+    /// The terminal settings are changed with `stty`, reads returning after a tenth of
+    /// a second without input, so a timeout can be enforced without threads.
+    pub struct Tty {
+        file: File,
+        saved: String,
+    }
+
+    impl Tty {
+        /// Opens the controlling terminal and switches it to raw mode.
+        pub fn open() -> io::Result<Tty> {
+            let file = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+            let saved = stty(&file, &["-g"])?;
+            stty(&file, &["raw", "-echo", "min", "0", "time", "1"])?;
+            Ok(Tty { file, saved })
+        }
+    }
+
+    impl Query<Tty, File> {
+        /// Creates a query talking to the controlling terminal.
+        pub fn tty() -> io::Result<Self> {
+            let tty = Tty::open()?;
+            let writer = tty.file.try_clone()?;
+            Ok(Query::new(tty, writer))
+        }
+    }
+
+    impl ReadTimeout for Tty {
+        fn read_timeout(&mut self, buffer: &mut [u8], timeout: Duration) -> io::Result<usize> {
+            let deadline = Instant::now() + timeout;
+            loop {
+                let read = self.file.read(buffer)?;
+                if read > 0 || Instant::now() >= deadline {
+                    return Ok(read);
+                }
+            }
+        }
+    }
+
+    impl Drop for Tty {
+        fn drop(&mut self) {
+            let _ = stty(&self.file, &[self.saved.trim()]);
+        }
+    }
+
+    /// Runs `stty` on the terminal and returns its output.
+    fn stty(file: &File, arguments: &[&str]) -> io::Result<String> {
+        let output = Command::new("stty")
+            .args(arguments)
+            .stdin(Stdio::from(file.try_clone()?))
+            .stderr(Stdio::null())
+            .output()?;
+        if !output.status.success() {
+            return Err(io::Error::new(io::ErrorKind::Other, "stty failed"));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
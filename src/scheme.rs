@@ -0,0 +1,142 @@
+/*!
+# scheme
+This module derives whole sets of colors from a single base color: the classic color
+wheel harmonies, rotating the HSL hue, tint, shade and tone scales, mixing the base
+with white, black or gray, and categorical palettes whose colors are as distinct from
+each other as possible, for charts with several series.
+
+# Examples
+
+```
+use rustcolor::color::RGB;
+use rustcolor::printer::ColorPrinter;
+use rustcolor::scheme;
+
+let brand = RGB(255, 0, 0);
+assert_eq!([brand, RGB(0, 255, 255)], scheme::complementary(brand));
+assert_eq!(
+    [brand, RGB(0, 255, 0), RGB(0, 0, 255)],
+    scheme::triadic(brand)
+);
+assert_eq!(
+    vec![brand, RGB(255, 128, 128)],
+    scheme::tints(brand, 2)
+);
+
+for color in scheme::categorical(brand, 5) {
+    println!("{}", "series".print_24bit(color, RGB(0, 0, 0)));
+}
+```
+*/
+
+use crate::color::RGB;
+use crate::gradient::{mix, Interpolation};
+use crate::space::Oklab;
+
+/// The lightness band of the categorical colors, readable on dark and light backgrounds.
+const CATEGORICAL_LIGHTNESS: (f64, f64) = (0.45, 0.85);
+/// The component step of the candidate colors of a categorical palette.
+const CATEGORICAL_STEP: usize = 17;
+
+/// Returns the base color and the opposite hue.
+pub fn complementary(base: RGB) -> [RGB; 2] {
+    [base, base.rotate_hue(180.0)]
+}
+
+/// Returns the base color and its two neighbours, 30 degrees apart.
+pub fn analogous(base: RGB) -> [RGB; 3] {
+    [base, base.rotate_hue(-30.0), base.rotate_hue(30.0)]
+}
+
+/// Returns three hues evenly spaced around the color wheel.
+pub fn triadic(base: RGB) -> [RGB; 3] {
+    [base, base.rotate_hue(120.0), base.rotate_hue(240.0)]
+}
+
+/// Returns two complementary pairs forming a rectangle on the color wheel.
+pub fn tetradic(base: RGB) -> [RGB; 4] {
+    [
+        base,
+        base.rotate_hue(60.0),
+        base.rotate_hue(180.0),
+        base.rotate_hue(240.0),
+    ]
+}
+
+/// Returns the base color and the two neighbours of its complement.
+pub fn split_complementary(base: RGB) -> [RGB; 3] {
+    [base, base.rotate_hue(150.0), base.rotate_hue(210.0)]
+}
+
+/// Returns `steps` colors from the base color toward white, white excluded.
+pub fn tints(base: RGB, steps: usize) -> Vec<RGB> {
+    scale(base, RGB(255, 255, 255), steps)
+}
+
+/// Returns `steps` colors from the base color toward black, black excluded.
+pub fn shades(base: RGB, steps: usize) -> Vec<RGB> {
+    scale(base, RGB(0, 0, 0), steps)
+}
+
+/// Returns `steps` colors from the base color toward gray, gray excluded.
+pub fn tones(base: RGB, steps: usize) -> Vec<RGB> {
+    scale(base, RGB(128, 128, 128), steps)
+}
+
+/// Returns `count` colors, starting with the base color, each one chosen as far as
+/// possible in OKLab from the previous ones, within a lightness band readable on both
+/// dark and light backgrounds. The colors are all distinct: fewer than `count` are
+/// returned once the candidates, a grid of the RGB cube, are exhausted.
+pub fn categorical(base: RGB, count: usize) -> Vec<RGB> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let base_lab = Oklab::from(base);
+    let levels = (0..=255).step_by(CATEGORICAL_STEP).map(|level| level as u8);
+    let mut candidates: Vec<(RGB, Oklab, f64)> = Vec::new();
+    for r in levels.clone() {
+        for g in levels.clone() {
+            for b in levels.clone() {
+                let color = RGB(r, g, b);
+                let lab = Oklab::from(color);
+                if color != base
+                    && lab.l >= CATEGORICAL_LIGHTNESS.0
+                    && lab.l <= CATEGORICAL_LIGHTNESS.1
+                {
+                    candidates.push((color, lab, lab.distance(&base_lab)));
+                }
+            }
+        }
+    }
+    let mut colors = Vec::with_capacity(count.min(candidates.len() + 1));
+    colors.push(base);
+    while colors.len() < count {
+        let farthest = candidates
+            .iter()
+            .enumerate()
+            .max_by(|a, b| (a.1).2.total_cmp(&(b.1).2))
+            .map(|(index, _)| index);
+        let (color, lab, _) = match farthest {
+            Some(index) => candidates.remove(index),
+            None => break,
+        };
+        colors.push(color);
+        for candidate in candidates.iter_mut() {
+            candidate.2 = candidate.2.min(candidate.1.distance(&lab));
+        }
+    }
+    colors
+}
+
+fn scale(base: RGB, target: RGB, steps: usize) -> Vec<RGB> {
+    (0..steps)
+        .map(|step| {
+            mix(
+                base,
+                target,
+                step as f64 / steps as f64,
+                Interpolation::Srgb,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,212 @@
+/*!
+# serde_impls
+This module implements `serde` support, enabled by the `serde` feature. Colors and
+styles are written as the strings their `FromStr` implementations parse, such as
+`"#ff8800"`, `"bright-red"` or `"bold #ff8800 on grey23"`. Styles can also be read
+from tables of `foreground`, `background` and attribute flags, and themes are tables
+mapping role names to styles. A deserialized theme holds only the roles of its table,
+to be merged over a preset with [`Theme::merge`].
+*/
+
+use crate::color::{Color, RGB};
+use crate::style::TextStyle;
+use crate::theme::{Role, Theme};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::fmt;
+
+/// The fields of a style table, aliases included.
+const STYLE_FIELDS: &[&str] = &[
+    "foreground",
+    "fg",
+    "background",
+    "bg",
+    "bold",
+    "faint",
+    "dim",
+    "italic",
+    "underline",
+    "blink",
+    "reverse",
+    "hidden",
+    "strikethrough",
+];
+
+impl Serialize for RGB {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RGB {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RgbVisitor;
+
+        impl<'de> Visitor<'de> for RgbVisitor {
+            type Value = RGB;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a color such as \"#ff8800\" or \"tomato\", or [r, g, b]")
+            }
+
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<RGB, E> {
+                match text.parse() {
+                    Ok(color) => Ok(color),
+                    Err(error) => crate::names::lookup(text).ok_or_else(|| E::custom(error)),
+                }
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<RGB, A::Error> {
+                let mut components = [0u8; 3];
+                for (index, component) in components.iter_mut().enumerate() {
+                    *component = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(index, &self))?;
+                }
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(4, &self));
+                }
+                Ok(RGB(components[0], components[1], components[2]))
+            }
+        }
+
+        deserializer.deserialize_any(RgbVisitor)
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ColorVisitor;
+
+        impl<'de> Visitor<'de> for ColorVisitor {
+            type Value = Color;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "a color such as \"red\", \"bright-blue\", \"#ff8800\" or a 256 color index"
+                )
+            }
+
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Color, E> {
+                text.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: de::Error>(self, index: u64) -> Result<Color, E> {
+                if index > 255 {
+                    return Err(E::invalid_value(de::Unexpected::Unsigned(index), &self));
+                }
+                Ok(Color::C256(index as u8))
+            }
+
+            fn visit_i64<E: de::Error>(self, index: i64) -> Result<Color, E> {
+                if index < 0 {
+                    return Err(E::invalid_value(de::Unexpected::Signed(index), &self));
+                }
+                self.visit_u64(index as u64)
+            }
+        }
+
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+impl Serialize for TextStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StyleVisitor;
+
+        impl<'de> Visitor<'de> for StyleVisitor {
+            type Value = TextStyle;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "a style such as \"bold #ff8800 on grey23\", or a table of colors and attributes"
+                )
+            }
+
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<TextStyle, E> {
+                text.parse()
+                    .map_err(|error| E::custom(format_args!("invalid style '{}': {}", text, error)))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<TextStyle, A::Error> {
+                let mut style = TextStyle::default();
+                while let Some(field) = map.next_key::<String>()? {
+                    match field.as_str() {
+                        "foreground" | "fg" => style.foreground = Some(map.next_value()?),
+                        "background" | "bg" => style.background = Some(map.next_value()?),
+                        "bold" => style.bold = map.next_value()?,
+                        "faint" | "dim" => style.faint = map.next_value()?,
+                        "italic" => style.italic = map.next_value()?,
+                        "underline" => style.underline = map.next_value()?,
+                        "blink" => style.blink = map.next_value()?,
+                        "reverse" => style.reverse = map.next_value()?,
+                        "hidden" => style.hidden = map.next_value()?,
+                        "strikethrough" => style.strikethrough = map.next_value()?,
+                        _ => return Err(de::Error::unknown_field(&field, STYLE_FIELDS)),
+                    }
+                }
+                Ok(style)
+            }
+        }
+
+        deserializer.deserialize_any(StyleVisitor)
+    }
+}
+
+impl Serialize for Theme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let styles = self.styles();
+        let mut map = serializer.serialize_map(Some(styles.len()))?;
+        for (name, style) in styles.iter() {
+            map.serialize_entry(name, style)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Theme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ThemeVisitor;
+
+        impl<'de> Visitor<'de> for ThemeVisitor {
+            type Value = Theme;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a table of role names and styles")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Theme, A::Error> {
+                let mut theme = Theme::new();
+                while let Some(name) = map.next_key::<String>()? {
+                    let role = Role::from_name(&name).ok_or_else(|| {
+                        de::Error::custom(format_args!(
+                            "unknown role '{}', expected a built-in or registered role",
+                            name
+                        ))
+                    })?;
+                    let style = map.next_value::<TextStyle>().map_err(|error| {
+                        de::Error::custom(format_args!("role '{}': {}", name, error))
+                    })?;
+                    theme = theme.set(role, style);
+                }
+                Ok(theme)
+            }
+        }
+
+        deserializer.deserialize_map(ThemeVisitor)
+    }
+}
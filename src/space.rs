@@ -0,0 +1,554 @@
+/*!
+# space
+This module implements color spaces other than RGB, with conversions to and from
+[`RGB`]. Hues are in degrees in 0-360, the other components in 0.0-1.0, and
+converting back to RGB rounds to the nearest value, so an RGB color survives a
+round trip unchanged.
+
+The HSL space gives real lighten, darken, saturate, desaturate and hue rotation
+operations for 24bit colors.
+
+The perceptual spaces, CIELAB, CIELCh, OKLab and OKLCh, go through linear sRGB and
+CIE XYZ with the D65 white point. Distances in them follow how different colors look,
+which the Delta E metrics of [`Lab`] measure.
+
+# Examples
+
+```
+use rustcolor::color::RGB;
+use rustcolor::space::Hsl;
+
+let hsl = Hsl::from(RGB(255, 0, 0));
+assert_eq!(Hsl::new(0.0, 1.0, 0.5), hsl);
+assert_eq!(RGB(0, 255, 0), RGB(255, 0, 0).rotate_hue(120.0));
+assert_eq!(RGB(255, 102, 102), RGB(255, 0, 0).lighten(0.2));
+```
+
+```
+use rustcolor::color::RGB;
+use rustcolor::space::Lab;
+
+let orange = Lab::from(RGB(255, 136, 0));
+let brown = Lab::from(RGB(170, 90, 0));
+assert!(orange.delta_e_2000(&brown) > 10.0);
+assert_eq!(RGB(255, 136, 0), RGB::from(orange));
+```
+*/
+
+use crate::color::RGB;
+
+/// The D65 reference white in XYZ.
+const D65: (f64, f64, f64) = (0.950_47, 1.0, 1.088_83);
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+
+/// A color in the hue, saturation and lightness space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+/// A color in the hue, saturation and value space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    pub hue: f64,
+    pub saturation: f64,
+    pub value: f64,
+}
+
+/// A color in the sRGB space without gamma encoding, with components in 0.0-1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearRgb {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+/// A color in the CIE 1931 XYZ space, with Y in 0.0-1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Xyz {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// A color in the CIELAB space, with lightness in 0-100.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// A color in the CIELCh space, the cylindrical form of CIELAB.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+/// A color in the OKLab space, with lightness in 0.0-1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// A color in the OKLCh space, the cylindrical form of OKLab.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+impl Hsl {
+    /// Creates a color, wrapping the hue into 0-360 and clamping the other components.
+    pub fn new(hue: f64, saturation: f64, lightness: f64) -> Self {
+        Self {
+            hue: normalize_hue(hue),
+            saturation: saturation.clamp(0.0, 1.0),
+            lightness: lightness.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl Hsv {
+    /// Creates a color, wrapping the hue into 0-360 and clamping the other components.
+    pub fn new(hue: f64, saturation: f64, value: f64) -> Self {
+        Self {
+            hue: normalize_hue(hue),
+            saturation: saturation.clamp(0.0, 1.0),
+            value: value.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl From<RGB> for Hsl {
+    fn from(color: RGB) -> Self {
+        let (r, g, b) = unit(color);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let lightness = (max + min) / 2.0;
+        let saturation = if chroma == 0.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        Hsl::new(hue(r, g, b, max, chroma), saturation, lightness)
+    }
+}
+
+impl From<Hsl> for RGB {
+    fn from(color: Hsl) -> Self {
+        let chroma = (1.0 - (2.0 * color.lightness - 1.0).abs()) * color.saturation;
+        from_chroma(color.hue, chroma, color.lightness - chroma / 2.0)
+    }
+}
+
+impl From<RGB> for Hsv {
+    fn from(color: RGB) -> Self {
+        let (r, g, b) = unit(color);
+        let max = r.max(g).max(b);
+        let chroma = max - r.min(g).min(b);
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+        Hsv::new(hue(r, g, b, max, chroma), saturation, max)
+    }
+}
+
+impl From<Hsv> for RGB {
+    fn from(color: Hsv) -> Self {
+        let chroma = color.value * color.saturation;
+        from_chroma(color.hue, chroma, color.value - chroma)
+    }
+}
+
+impl From<Hsv> for Hsl {
+    fn from(color: Hsv) -> Self {
+        let lightness = color.value * (1.0 - color.saturation / 2.0);
+        let saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            (color.value - lightness) / lightness.min(1.0 - lightness)
+        };
+        Hsl::new(color.hue, saturation, lightness)
+    }
+}
+
+impl From<Hsl> for Hsv {
+    fn from(color: Hsl) -> Self {
+        let value = color.lightness + color.saturation * color.lightness.min(1.0 - color.lightness);
+        let saturation = if value == 0.0 {
+            0.0
+        } else {
+            2.0 * (1.0 - color.lightness / value)
+        };
+        Hsv::new(color.hue, saturation, value)
+    }
+}
+
+impl From<RGB> for LinearRgb {
+    fn from(color: RGB) -> Self {
+        let (r, g, b) = unit(color);
+        let linear = |c: f64| {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        LinearRgb {
+            r: linear(r),
+            g: linear(g),
+            b: linear(b),
+        }
+    }
+}
+
+impl From<LinearRgb> for RGB {
+    fn from(color: LinearRgb) -> Self {
+        let gamma = |c: f64| {
+            if c <= 0.003_130_8 {
+                12.92 * c
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            }
+        };
+        from_unit(gamma(color.r), gamma(color.g), gamma(color.b))
+    }
+}
+
+impl From<LinearRgb> for Xyz {
+    fn from(LinearRgb { r, g, b }: LinearRgb) -> Self {
+        Xyz {
+            x: 0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+            y: 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b,
+            z: 0.019_333_9 * r + 0.119_192_0 * g + 0.950_304_1 * b,
+        }
+    }
+}
+
+impl From<Xyz> for LinearRgb {
+    fn from(Xyz { x, y, z }: Xyz) -> Self {
+        LinearRgb {
+            r: 3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+            g: -0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+            b: 0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+        }
+    }
+}
+
+impl From<Xyz> for Lab {
+    fn from(color: Xyz) -> Self {
+        let f = |t: f64| {
+            if t > LAB_EPSILON {
+                t.cbrt()
+            } else {
+                (LAB_KAPPA * t + 16.0) / 116.0
+            }
+        };
+        let fx = f(color.x / D65.0);
+        let fy = f(color.y / D65.1);
+        let fz = f(color.z / D65.2);
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+impl From<Lab> for Xyz {
+    fn from(color: Lab) -> Self {
+        let fy = (color.l + 16.0) / 116.0;
+        let fx = color.a / 500.0 + fy;
+        let fz = fy - color.b / 200.0;
+        let f_inverse = |f: f64| {
+            if f.powi(3) > LAB_EPSILON {
+                f.powi(3)
+            } else {
+                (116.0 * f - 16.0) / LAB_KAPPA
+            }
+        };
+        let y = if color.l > LAB_KAPPA * LAB_EPSILON {
+            fy.powi(3)
+        } else {
+            color.l / LAB_KAPPA
+        };
+        Xyz {
+            x: f_inverse(fx) * D65.0,
+            y: y * D65.1,
+            z: f_inverse(fz) * D65.2,
+        }
+    }
+}
+
+impl From<Lab> for Lch {
+    fn from(Lab { l, a, b }: Lab) -> Self {
+        let (c, h) = polar(a, b);
+        Lch { l, c, h }
+    }
+}
+
+impl From<Lch> for Lab {
+    fn from(Lch { l, c, h }: Lch) -> Self {
+        let (a, b) = cartesian(c, h);
+        Lab { l, a, b }
+    }
+}
+
+impl From<LinearRgb> for Oklab {
+    fn from(LinearRgb { r, g, b }: LinearRgb) -> Self {
+        let l = (0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b).cbrt();
+        let m = (0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b).cbrt();
+        let s = (0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b).cbrt();
+        Oklab {
+            l: 0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+            a: 1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+            b: 0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s,
+        }
+    }
+}
+
+impl From<Oklab> for LinearRgb {
+    fn from(color: Oklab) -> Self {
+        let l = (color.l + 0.396_337_777_4 * color.a + 0.215_803_757_3 * color.b).powi(3);
+        let m = (color.l - 0.105_561_345_8 * color.a - 0.063_854_172_8 * color.b).powi(3);
+        let s = (color.l - 0.089_484_177_5 * color.a - 1.291_485_548_0 * color.b).powi(3);
+        LinearRgb {
+            r: 4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s,
+            g: -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s,
+            b: -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s,
+        }
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(Oklab { l, a, b }: Oklab) -> Self {
+        let (c, h) = polar(a, b);
+        Oklch { l, c, h }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(Oklch { l, c, h }: Oklch) -> Self {
+        let (a, b) = cartesian(c, h);
+        Oklab { l, a, b }
+    }
+}
+
+/// Implements the conversions between RGB and a space reached through other spaces.
+macro_rules! impl_rgb_conversions {
+    ($space:ty, $($through:ty),+) => {
+        impl From<RGB> for $space {
+            fn from(color: RGB) -> Self {
+                $(let color = <$through>::from(color);)+
+                <$space>::from(color)
+            }
+        }
+
+        impl From<$space> for RGB {
+            fn from(color: $space) -> Self {
+                impl_rgb_conversions!(@back color, $($through),+);
+                RGB::from(color)
+            }
+        }
+    };
+    (@back $color:ident, $through:ty) => {
+        let $color = <$through>::from($color);
+    };
+    (@back $color:ident, $through:ty, $($rest:ty),+) => {
+        impl_rgb_conversions!(@back $color, $($rest),+);
+        let $color = <$through>::from($color);
+    };
+}
+
+impl_rgb_conversions!(Xyz, LinearRgb);
+impl_rgb_conversions!(Lab, LinearRgb, Xyz);
+impl_rgb_conversions!(Lch, LinearRgb, Xyz, Lab);
+impl_rgb_conversions!(Oklab, LinearRgb);
+impl_rgb_conversions!(Oklch, LinearRgb, Oklab);
+
+impl Lab {
+    /// The CIE76 color difference, the euclidean distance in CIELAB.
+    pub fn delta_e_76(&self, other: &Lab) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2))
+            .sqrt()
+    }
+
+    /// The CIE94 color difference, with the graphic arts weights.
+    pub fn delta_e_94(&self, other: &Lab) -> f64 {
+        let c1 = self.a.hypot(self.b);
+        let c2 = other.a.hypot(other.b);
+        let delta_l = self.l - other.l;
+        let delta_c = c1 - c2;
+        let delta_h_squared =
+            ((self.a - other.a).powi(2) + (self.b - other.b).powi(2) - delta_c.powi(2)).max(0.0);
+        let sc = 1.0 + 0.045 * c1;
+        let sh = 1.0 + 0.015 * c1;
+        (delta_l.powi(2) + (delta_c / sc).powi(2) + delta_h_squared / sh.powi(2)).sqrt()
+    }
+
+    /// The CIEDE2000 color difference.
+    pub fn delta_e_2000(&self, other: &Lab) -> f64 {
+        let c_mean = (self.a.hypot(self.b) + other.a.hypot(other.b)) / 2.0;
+        let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt());
+        let a1 = self.a * (1.0 + g);
+        let a2 = other.a * (1.0 + g);
+        let c1 = a1.hypot(self.b);
+        let c2 = a2.hypot(other.b);
+        let h1 = if c1 == 0.0 {
+            0.0
+        } else {
+            normalize_hue(self.b.atan2(a1).to_degrees())
+        };
+        let h2 = if c2 == 0.0 {
+            0.0
+        } else {
+            normalize_hue(other.b.atan2(a2).to_degrees())
+        };
+
+        let delta_l = other.l - self.l;
+        let delta_c = c2 - c1;
+        let delta_h = if c1 * c2 == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 - h1 > 180.0 {
+            h2 - h1 - 360.0
+        } else {
+            h2 - h1 + 360.0
+        };
+        let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+        let l_mean = (self.l + other.l) / 2.0;
+        let c_mean = (c1 + c2) / 2.0;
+        let h_mean = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+            + 0.24 * (2.0 * h_mean).to_radians().cos()
+            + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+            - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+        let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+        let rc = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt();
+        let sl = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+        let sc = 1.0 + 0.045 * c_mean;
+        let sh = 1.0 + 0.015 * c_mean * t;
+        let rt = -(2.0 * delta_theta).to_radians().sin() * rc;
+
+        ((delta_l / sl).powi(2)
+            + (delta_c / sc).powi(2)
+            + (delta_h / sh).powi(2)
+            + rt * (delta_c / sc) * (delta_h / sh))
+            .sqrt()
+    }
+}
+
+impl Oklab {
+    /// The euclidean distance in OKLab, a simple perceptual color difference.
+    pub fn distance(&self, other: &Oklab) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2))
+            .sqrt()
+    }
+}
+
+impl RGB {
+    /// Increases the HSL lightness by an amount in 0.0-1.0.
+    pub fn lighten(self, amount: f64) -> RGB {
+        let hsl = Hsl::from(self);
+        Hsl::new(hsl.hue, hsl.saturation, hsl.lightness + amount).into()
+    }
+
+    /// Decreases the HSL lightness by an amount in 0.0-1.0.
+    pub fn darken(self, amount: f64) -> RGB {
+        self.lighten(-amount)
+    }
+
+    /// Increases the HSL saturation by an amount in 0.0-1.0.
+    pub fn saturate(self, amount: f64) -> RGB {
+        let hsl = Hsl::from(self);
+        Hsl::new(hsl.hue, hsl.saturation + amount, hsl.lightness).into()
+    }
+
+    /// Decreases the HSL saturation by an amount in 0.0-1.0.
+    pub fn desaturate(self, amount: f64) -> RGB {
+        self.saturate(-amount)
+    }
+
+    /// Rotates the hue by an angle in degrees.
+    pub fn rotate_hue(self, degrees: f64) -> RGB {
+        let hsl = Hsl::from(self);
+        Hsl::new(hsl.hue + degrees, hsl.saturation, hsl.lightness).into()
+    }
+}
+
+/// Wraps a hue into 0-360.
+pub(crate) fn normalize_hue(hue: f64) -> f64 {
+    let hue = hue.rem_euclid(360.0);
+    if hue.is_nan() || hue >= 360.0 {
+        0.0
+    } else {
+        hue
+    }
+}
+
+/// Converts a color to components in 0.0-1.0.
+pub(crate) fn unit(RGB(r, g, b): RGB) -> (f64, f64, f64) {
+    (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
+}
+
+/// Converts components in 0.0-1.0 to a color, rounding and clamping them.
+pub(crate) fn from_unit(r: f64, g: f64, b: f64) -> RGB {
+    let byte = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    RGB(byte(r), byte(g), byte(b))
+}
+
+/// Converts cartesian chroma axes to chroma and hue in degrees.
+fn polar(a: f64, b: f64) -> (f64, f64) {
+    (a.hypot(b), normalize_hue(b.atan2(a).to_degrees()))
+}
+
+/// Converts chroma and hue in degrees to cartesian chroma axes.
+fn cartesian(c: f64, h: f64) -> (f64, f64) {
+    let h = h.to_radians();
+    (c * h.cos(), c * h.sin())
+}
+
+fn hue(r: f64, g: f64, b: f64, max: f64, chroma: f64) -> f64 {
+    if chroma == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / chroma)
+    } else if max == g {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    }
+}
+
+fn from_chroma(hue: f64, chroma: f64, offset: f64) -> RGB {
+    let sector = normalize_hue(hue) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    from_unit(r + offset, g + offset, b + offset)
+}
@@ -7,6 +7,10 @@ Thre are rules that **must be followed** to build a style:
 * All styles may have a background8bit tag in any order, but followed by color tag and a required(if background is the last tag) eos tag
 * All styles may have a foreground8bit tag in any order, but followed by color tag and a required(if foreground is the last tag) eos tag
 
+The built [`Style`] is a literal template, rendered byte for byte as it was built.
+A [`TextStyle`] holds the decoded colors and attributes of SGR tags instead, and
+renders them back as colors first, then attributes.
+
 # Examples
 
 * The below example builds a red fg default bg style.
@@ -29,6 +33,12 @@ let result = StyleBuilder::new()
 assert_eq!(expected, result.render("this is a red foreground color text"));
 ```
  */
+use crate::color::{Color, ParseColorError, RGB};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
 const CSI: &str = "\u{001b}[";
 const BACKGROUND_8BIT: &str = "48;5";
 const FOREGROUND_8BIT: &str = "38;5";
@@ -134,6 +144,12 @@ impl StyleBuilder {
     }
 }
 
+impl Default for StyleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A style that has a formatted string
 pub struct Style {
     message: String,
@@ -145,3 +161,515 @@ impl Style {
         self.message.replace(MESSAGE, message)
     }
 }
+
+/// A style, as the set of colors and attributes selected by SGR tags.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TextStyle {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub faint: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub blink: bool,
+    pub reverse: bool,
+    pub hidden: bool,
+    pub strikethrough: bool,
+}
+
+impl TextStyle {
+    /// Parses a SGR parameter list, as used by `LS_COLORS` and `GREP_COLORS`,
+    /// into a style. Colon separated sub parameters are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::color::{Color, RGB};
+    /// use rustcolor::style::TextStyle;
+    ///
+    /// let style = TextStyle::from_sgr("1;38;5;208;48;2;10;10;10").unwrap();
+    /// assert!(style.bold);
+    /// assert_eq!(Some(Color::C256(208)), style.foreground);
+    /// assert_eq!(Some(Color::Rgb(RGB(10, 10, 10))), style.background);
+    /// assert_eq!("38;5;208;48;2;10;10;10;1", style.to_sgr());
+    /// ```
+    pub fn from_sgr(sgr: &str) -> Result<TextStyle, SgrError> {
+        let params = split_params(sgr)?;
+        let mut style = TextStyle::default();
+        let mut index = 0;
+        while index < params.len() {
+            style.apply(&next_sgr(&params, &mut index)?);
+        }
+        Ok(style)
+    }
+
+    /// Parses a git color specification, as in `git config color.diff.new "bold green"`:
+    /// attributes, negated with a `no` or `no-` prefix, then a foreground and a
+    /// background color. `normal` and `-1` leave a color unset, numbers are 256 color
+    /// indexes, and `reset` and `autoreset` are accepted, the rendered styles always
+    /// starting from a reset and ending with one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::color::Color;
+    /// use rustcolor::style::TextStyle;
+    ///
+    /// let style = TextStyle::from_git("bold red ul").unwrap();
+    /// assert!(style.bold && style.underline);
+    /// assert_eq!(Some(Color::C16(1)), style.foreground);
+    ///
+    /// let style = TextStyle::from_git("normal 236 nobold").unwrap();
+    /// assert_eq!(None, style.foreground);
+    /// assert_eq!(Some(Color::C256(236)), style.background);
+    /// ```
+    pub fn from_git(spec: &str) -> Result<TextStyle, ParseStyleError> {
+        let mut style = TextStyle::default();
+        let mut colors = 0;
+        for word in spec.split_whitespace() {
+            let lowercase = word.to_ascii_lowercase();
+            if lowercase == "reset" || lowercase == "autoreset" {
+                continue;
+            }
+            if let Some(attribute) = style.attribute(&lowercase) {
+                *attribute = true;
+                continue;
+            }
+            let negated = lowercase
+                .strip_prefix("no-")
+                .or_else(|| lowercase.strip_prefix("no"));
+            if let Some(attribute) = negated.and_then(|name| style.attribute(name)) {
+                *attribute = false;
+                continue;
+            }
+            let color = match lowercase.as_str() {
+                "normal" | "-1" => None,
+                _ => match word.parse::<Color>() {
+                    Ok(color) => Some(color),
+                    Err(ParseColorError::UnknownFormat(_)) => {
+                        return Err(ParseStyleError::UnknownWord(word.to_owned()))
+                    }
+                    Err(error) => return Err(ParseStyleError::InvalidColor(error)),
+                },
+            };
+            match colors {
+                0 => style.foreground = color,
+                1 => style.background = color,
+                _ => return Err(ParseStyleError::UnexpectedColor(word.to_owned())),
+            }
+            colors += 1;
+        }
+        Ok(style)
+    }
+
+    /// Serializes the style as a SGR parameter list, colors first, then attributes.
+    /// An empty style serializes to an empty string.
+    pub fn to_sgr(&self) -> String {
+        self.params().join(&DELIMITER.to_string())
+    }
+
+    /// Renders the message with the style, followed by a reset. An empty style
+    /// leaves the message unchanged.
+    pub fn render(&self, message: &str) -> String {
+        let params = self.to_sgr();
+        if params.is_empty() {
+            return message.to_owned();
+        }
+        format!(
+            "{}{}{}{}{}{}{}",
+            CSI, params, END_SGR, message, CSI, RESET, END_SGR
+        )
+    }
+
+    /// Applies a graphic rendition change to the style.
+    pub fn apply(&mut self, change: &Sgr) {
+        match change {
+            Sgr::Reset => *self = TextStyle::default(),
+            Sgr::Bold => self.bold = true,
+            Sgr::Faint => self.faint = true,
+            Sgr::Italic => self.italic = true,
+            Sgr::Underline => self.underline = true,
+            Sgr::Blink => self.blink = true,
+            Sgr::Reverse => self.reverse = true,
+            Sgr::Hidden => self.hidden = true,
+            Sgr::Strikethrough => self.strikethrough = true,
+            Sgr::NormalIntensity => {
+                self.bold = false;
+                self.faint = false;
+            }
+            Sgr::NoItalic => self.italic = false,
+            Sgr::NoUnderline => self.underline = false,
+            Sgr::NoBlink => self.blink = false,
+            Sgr::NoReverse => self.reverse = false,
+            Sgr::NoHidden => self.hidden = false,
+            Sgr::NoStrikethrough => self.strikethrough = false,
+            Sgr::Foreground(color) => self.foreground = Some(*color),
+            Sgr::Background(color) => self.background = Some(*color),
+            Sgr::Unknown(_) => {}
+        }
+    }
+
+    /// Returns the SGR parameters of the style: colors first, then attributes.
+    fn params(&self) -> Vec<String> {
+        let mut params = Vec::new();
+        if let Some(color) = self.foreground {
+            params.push(color.foreground_sgr());
+        }
+        if let Some(color) = self.background {
+            params.push(color.background_sgr());
+        }
+        let attributes = [
+            (self.bold, '1'),
+            (self.faint, '2'),
+            (self.italic, '3'),
+            (self.underline, UNDERLINE),
+            (self.blink, BLINK),
+            (self.reverse, '7'),
+            (self.hidden, '8'),
+            (self.strikethrough, '9'),
+        ];
+        for (enabled, tag) in attributes.iter() {
+            if *enabled {
+                params.push(tag.to_string());
+            }
+        }
+        params
+    }
+}
+
+impl FromStr for TextStyle {
+    type Err = ParseStyleError;
+
+    /// Parses a style written in words, such as `bold italic #ff8800 on grey23`:
+    /// attributes, then an optional foreground color and an optional background color
+    /// after `on`, in any [`Color`] notation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustcolor::color::{Color, RGB};
+    /// use rustcolor::style::TextStyle;
+    ///
+    /// let style: TextStyle = "bold #ff8800 on grey23".parse().unwrap();
+    /// assert!(style.bold);
+    /// assert_eq!(Some(Color::Rgb(RGB(255, 136, 0))), style.foreground);
+    /// assert_eq!(Some(Color::Rgb(RGB(59, 59, 59))), style.background);
+    /// assert_eq!("bold #ff8800 on #3b3b3b", style.to_string());
+    /// ```
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut style = TextStyle::default();
+        let mut words = text.split_whitespace();
+        while let Some(word) = words.next() {
+            let lowercase = word.to_ascii_lowercase();
+            if let Some(attribute) = style.attribute(&lowercase) {
+                *attribute = true;
+                continue;
+            }
+            let background = lowercase == "on";
+            let word = if background {
+                words.next().ok_or(ParseStyleError::MissingBackground)?
+            } else {
+                word
+            };
+            let color = match word.parse::<Color>() {
+                Ok(color) => color,
+                Err(ParseColorError::UnknownFormat(_)) => {
+                    return Err(ParseStyleError::UnknownWord(word.to_owned()))
+                }
+                Err(error) => return Err(ParseStyleError::InvalidColor(error)),
+            };
+            if style.background.is_some() || (!background && style.foreground.is_some()) {
+                return Err(ParseStyleError::UnexpectedColor(word.to_owned()));
+            }
+            if background {
+                style.background = Some(color);
+            } else {
+                style.foreground = Some(color);
+            }
+        }
+        Ok(style)
+    }
+}
+
+impl fmt::Display for TextStyle {
+    /// Formats the style in words, as [`TextStyle::from_str`] parses it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words: Vec<String> = ATTRIBUTE_NAMES
+            .iter()
+            .zip(self.attributes().iter())
+            .filter(|(_, enabled)| **enabled)
+            .map(|(names, _)| names[0].to_owned())
+            .collect();
+        if let Some(color) = self.foreground {
+            words.push(color.to_string());
+        }
+        if let Some(color) = self.background {
+            words.push(format!("on {}", color));
+        }
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// The names of the attributes, in the order of the SGR codes, preferred name first.
+const ATTRIBUTE_NAMES: [&[&str]; 8] = [
+    &["bold"],
+    &["faint", "dim"],
+    &["italic"],
+    &["underline", "ul"],
+    &["blink"],
+    &["reverse", "inverse"],
+    &["hidden", "conceal"],
+    &["strikethrough", "strike"],
+];
+
+impl TextStyle {
+    fn attributes(&self) -> [bool; 8] {
+        [
+            self.bold,
+            self.faint,
+            self.italic,
+            self.underline,
+            self.blink,
+            self.reverse,
+            self.hidden,
+            self.strikethrough,
+        ]
+    }
+
+    /// Returns the attribute flag of a lowercase attribute name.
+    fn attribute(&mut self, name: &str) -> Option<&mut bool> {
+        let index = ATTRIBUTE_NAMES
+            .iter()
+            .position(|names| names.contains(&name))?;
+        Some(match index {
+            0 => &mut self.bold,
+            1 => &mut self.faint,
+            2 => &mut self.italic,
+            3 => &mut self.underline,
+            4 => &mut self.blink,
+            5 => &mut self.reverse,
+            6 => &mut self.hidden,
+            _ => &mut self.strikethrough,
+        })
+    }
+}
+
+/// An error of [`TextStyle::from_str`] and [`TextStyle::from_git`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseStyleError {
+    /// A word that is neither an attribute, `on` nor a color.
+    UnknownWord(String),
+    /// A color that looks like a color notation, but is malformed.
+    InvalidColor(ParseColorError),
+    /// A second foreground or background, or a foreground after the background.
+    UnexpectedColor(String),
+    /// `on` ending the style.
+    MissingBackground,
+}
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseStyleError::UnknownWord(word) => {
+                write!(f, "unknown attribute or color '{}'", word)
+            }
+            ParseStyleError::InvalidColor(error) => error.fmt(f),
+            ParseStyleError::UnexpectedColor(word) => write!(
+                f,
+                "unexpected color '{}', a style has a single foreground and background",
+                word
+            ),
+            ParseStyleError::MissingBackground => write!(f, "missing background color after 'on'"),
+        }
+    }
+}
+
+impl Error for ParseStyleError {}
+
+/// A single change of graphic rendition, decoded from the SGR parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Sgr {
+    /// Tag 0, resets all attributes and colors.
+    Reset,
+    /// Tag 1.
+    Bold,
+    /// Tag 2.
+    Faint,
+    /// Tag 3.
+    Italic,
+    /// Tags 4 and 21.
+    Underline,
+    /// Tags 5 and 6.
+    Blink,
+    /// Tag 7.
+    Reverse,
+    /// Tag 8.
+    Hidden,
+    /// Tag 9.
+    Strikethrough,
+    /// Tag 22, neither bold nor faint.
+    NormalIntensity,
+    /// Tag 23.
+    NoItalic,
+    /// Tag 24.
+    NoUnderline,
+    /// Tag 25.
+    NoBlink,
+    /// Tag 27.
+    NoReverse,
+    /// Tag 28.
+    NoHidden,
+    /// Tag 29.
+    NoStrikethrough,
+    /// Tags 30-39 and 90-97.
+    Foreground(Color),
+    /// Tags 40-49 and 100-107.
+    Background(Color),
+    /// A parameter this library does not understand, with all its sub parameters.
+    Unknown(Vec<u16>),
+}
+
+impl Sgr {
+    /// Decodes a list of SGR parameters, where each parameter holds its colon
+    /// separated sub parameters. An empty list is the same as a reset.
+    pub fn decode(params: &[Vec<u16>]) -> Vec<Sgr> {
+        if params.is_empty() {
+            return vec![Sgr::Reset];
+        }
+        let mut changes = Vec::new();
+        let mut index = 0;
+        while index < params.len() {
+            let start = index;
+            let change = next_sgr(params, &mut index).unwrap_or_else(|_| {
+                Sgr::Unknown(params[start..index].iter().flatten().copied().collect())
+            });
+            changes.push(change);
+        }
+        changes
+    }
+}
+
+/// Error returned when a SGR parameter list can not be parsed into a style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SgrError {
+    /// A parameter that is not a number, or does not fit in 16 bits.
+    InvalidNumber(String),
+    /// A tag that is not supported.
+    UnknownCode(u16),
+    /// A tag with missing or out of range values, with all the values consumed.
+    OutOfRange(Vec<u16>),
+}
+
+impl fmt::Display for SgrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SgrError::InvalidNumber(param) => write!(f, "invalid SGR parameter '{}'", param),
+            SgrError::UnknownCode(code) => write!(f, "unknown SGR code {}", code),
+            SgrError::OutOfRange(values) => {
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                write!(f, "out of range SGR values '{}'", values.join(";"))
+            }
+        }
+    }
+}
+
+impl Error for SgrError {}
+
+/// Splits a SGR parameter string, as `1;38:5:208`, into parameters and their sub parameters.
+/// Empty values are zero, following ECMA-48.
+fn split_params(sgr: &str) -> Result<Vec<Vec<u16>>, SgrError> {
+    if sgr.is_empty() {
+        return Ok(Vec::new());
+    }
+    sgr.split(DELIMITER)
+        .map(|param| {
+            param
+                .split(':')
+                .map(|value| match value.trim() {
+                    "" => Ok(0),
+                    value => value
+                        .parse::<u16>()
+                        .map_err(|_| SgrError::InvalidNumber(param.to_owned())),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Decodes the parameter at `index`, advancing it past every parameter consumed.
+/// A parameter without value is zero, following ECMA-48.
+fn next_sgr(params: &[Vec<u16>], index: &mut usize) -> Result<Sgr, SgrError> {
+    let start = *index;
+    let param = &params[start];
+    *index += 1;
+    let code = first(param);
+    let change = match code {
+        0 => Sgr::Reset,
+        1 => Sgr::Bold,
+        2 => Sgr::Faint,
+        3 => Sgr::Italic,
+        4 => match param.get(1) {
+            Some(0) => Sgr::NoUnderline,
+            None | Some(1..=5) => Sgr::Underline,
+            Some(_) => return Err(SgrError::OutOfRange(param.clone())),
+        },
+        21 => Sgr::Underline,
+        5 | 6 => Sgr::Blink,
+        7 => Sgr::Reverse,
+        8 => Sgr::Hidden,
+        9 => Sgr::Strikethrough,
+        22 => Sgr::NormalIntensity,
+        23 => Sgr::NoItalic,
+        24 => Sgr::NoUnderline,
+        25 => Sgr::NoBlink,
+        27 => Sgr::NoReverse,
+        28 => Sgr::NoHidden,
+        29 => Sgr::NoStrikethrough,
+        38 | 48 | 58 => {
+            let color = if param.len() > 1 {
+                extended_color(&param[1..])
+            } else {
+                let len = match params.get(*index).map(|p| first(p)) {
+                    Some(5) => 2,
+                    Some(2) => 4,
+                    _ => 0,
+                };
+                let end = (*index + len).min(params.len());
+                let values: Vec<u16> = params[*index..end].iter().map(|p| first(p)).collect();
+                *index = end;
+                extended_color(&values)
+            };
+            match (code, color) {
+                (38, Some(color)) => Sgr::Foreground(color),
+                (48, Some(color)) => Sgr::Background(color),
+                (58, Some(_)) => return Err(SgrError::UnknownCode(code)),
+                _ => {
+                    let values = params[start..*index].iter().flatten().copied().collect();
+                    return Err(SgrError::OutOfRange(values));
+                }
+            }
+        }
+        30..=39 => Sgr::Foreground(Color::from_foreground_code(code as usize).unwrap()),
+        40..=49 => Sgr::Background(Color::from_background_code(code as usize).unwrap()),
+        90..=97 => Sgr::Foreground(Color::from_foreground_code(code as usize).unwrap()),
+        100..=107 => Sgr::Background(Color::from_background_code(code as usize).unwrap()),
+        _ => return Err(SgrError::UnknownCode(code)),
+    };
+    Ok(change)
+}
+
+/// Returns the value of a parameter, without its sub parameters.
+fn first(param: &[u16]) -> u16 {
+    param.first().copied().unwrap_or(0)
+}
+
+/// Decodes the values following an extended color tag, `5;n` or `2;r;g;b`,
+/// also accepting the color space id of the colon separated form.
+fn extended_color(values: &[u16]) -> Option<Color> {
+    let byte = |value: u16| u8::try_from(value).ok();
+    match *values {
+        [5, n] => byte(n).map(Color::C256),
+        [2, r, g, b] | [2, _, r, g, b, ..] => Some(Color::Rgb(RGB(byte(r)?, byte(g)?, byte(b)?))),
+        _ => None,
+    }
+}
@@ -0,0 +1,302 @@
+/*!
+# svg
+This module renders ANSI output into a self contained SVG terminal snapshot, so the
+screenshots of the documentation can be generated instead of captured by hand.
+Characters are laid out on a monospace grid, line feeds, carriage returns, tabs and
+backspaces move the cursor, and colors are resolved through a [`Palette`].
+
+# Examples
+
+```
+use rustcolor::printer::ColorPrinter;
+use rustcolor::svg::SvgRenderer;
+
+let svg = SvgRenderer::new()
+    .title("styles")
+    .render(&"this is the info style".info());
+
+assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+assert!(svg.contains("fill=\"#00cd00\">this is the info style</tspan>"));
+```
+*/
+
+use crate::html::escape;
+use crate::palette::Palette;
+use crate::parser::{parse, Token};
+use crate::style::TextStyle;
+
+const TAB_WIDTH: usize = 8;
+const CHROME_HEIGHT: f32 = 32.0;
+const CHROME_BUTTONS: [&str; 3] = ["#ff5f56", "#ffbd2e", "#27c93f"];
+
+/// Renders ANSI output into an SVG terminal snapshot.
+pub struct SvgRenderer {
+    palette: Palette,
+    font_family: String,
+    font_size: f32,
+    line_height: f32,
+    char_width: f32,
+    padding: f32,
+    columns: Option<usize>,
+    chrome: bool,
+    title: String,
+}
+
+impl SvgRenderer {
+    /// Creates a renderer with the default palette, a 14px monospace font and window chrome.
+    pub fn new() -> Self {
+        Self {
+            palette: Palette::default(),
+            font_family: "Menlo, Consolas, 'DejaVu Sans Mono', monospace".to_owned(),
+            font_size: 14.0,
+            line_height: 1.3,
+            char_width: 0.6,
+            padding: 12.0,
+            columns: None,
+            chrome: true,
+            title: String::new(),
+        }
+    }
+
+    /// Sets the palette used to resolve colors.
+    pub fn palette(mut self, palette: Palette) -> SvgRenderer {
+        self.palette = palette;
+        self
+    }
+
+    /// Sets the CSS font family of the text.
+    pub fn font_family(mut self, font_family: &str) -> SvgRenderer {
+        self.font_family = font_family.to_owned();
+        self
+    }
+
+    /// Sets the font size, in pixels.
+    pub fn font_size(mut self, font_size: f32) -> SvgRenderer {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the line height, relative to the font size.
+    pub fn line_height(mut self, line_height: f32) -> SvgRenderer {
+        self.line_height = line_height;
+        self
+    }
+
+    /// Sets the advance of a character, relative to the font size.
+    pub fn char_width(mut self, char_width: f32) -> SvgRenderer {
+        self.char_width = char_width;
+        self
+    }
+
+    /// Sets the space around the grid, in pixels.
+    pub fn padding(mut self, padding: f32) -> SvgRenderer {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the grid width, which defaults to the longest line.
+    pub fn columns(mut self, columns: usize) -> SvgRenderer {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Enables or disables the window title bar.
+    pub fn chrome(mut self, chrome: bool) -> SvgRenderer {
+        self.chrome = chrome;
+        self
+    }
+
+    /// Sets the title shown in the window title bar.
+    pub fn title(mut self, title: &str) -> SvgRenderer {
+        self.title = title.to_owned();
+        self
+    }
+
+    /// Renders ANSI output into an SVG document.
+    pub fn render(&self, input: &str) -> String {
+        let mut rows = layout(input);
+        while rows.len() > 1 && rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        let columns = self
+            .columns
+            .unwrap_or_else(|| rows.iter().map(|row| row.len()).max().unwrap_or(0));
+        let cell_width = self.font_size * self.char_width;
+        let cell_height = self.font_size * self.line_height;
+        let top = if self.chrome { CHROME_HEIGHT } else { 0.0 } + self.padding;
+        let width = columns as f32 * cell_width + 2.0 * self.padding;
+        let height = top + rows.len() as f32 * cell_height + self.padding;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+            px(width),
+            px(height)
+        );
+        svg.push_str(&format!(
+            "<style>text{{font-family:{};font-size:{}px;white-space:pre}}</style>\n",
+            escape(&self.font_family),
+            px(self.font_size)
+        ));
+        svg.push_str(&format!(
+            "<rect width=\"{}\" height=\"{}\" rx=\"6\" fill=\"{}\"/>\n",
+            px(width),
+            px(height),
+            self.palette.background
+        ));
+        if self.chrome {
+            for (index, color) in CHROME_BUTTONS.iter().enumerate() {
+                svg.push_str(&format!(
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"6\" fill=\"{}\"/>\n",
+                    px(20.0 + 20.0 * index as f32),
+                    px(CHROME_HEIGHT / 2.0),
+                    color
+                ));
+            }
+            if !self.title.is_empty() {
+                svg.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\" fill=\"{}\" opacity=\"0.6\">{}</text>\n",
+                    px(width / 2.0),
+                    px(CHROME_HEIGHT / 2.0),
+                    self.palette.foreground,
+                    escape(&self.title)
+                ));
+            }
+        }
+
+        for (row_index, row) in rows.iter().enumerate() {
+            let y = top + row_index as f32 * cell_height;
+            let runs = runs(row);
+            for (column, style, text) in runs.iter() {
+                let (_, background) = self.palette.resolve_style(style);
+                if background != self.palette.background {
+                    let length = text.chars().count();
+                    svg.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                        px(self.padding + *column as f32 * cell_width),
+                        px(y),
+                        px(length as f32 * cell_width),
+                        px(cell_height),
+                        background
+                    ));
+                }
+            }
+            if runs.iter().all(|(_, style, text)| !visible(style, text)) {
+                continue;
+            }
+            svg.push_str(&format!(
+                "<text y=\"{}\" dominant-baseline=\"middle\" xml:space=\"preserve\">",
+                px(y + cell_height / 2.0)
+            ));
+            for (column, style, text) in runs.iter() {
+                if !visible(style, text) {
+                    continue;
+                }
+                let mut column = *column;
+                let mut text = text.as_str();
+                if !style.underline && !style.strikethrough {
+                    let trimmed = text.trim_start_matches(' ');
+                    column += text.len() - trimmed.len();
+                    text = trimmed;
+                }
+                let (foreground, _) = self.palette.resolve_style(style);
+                let mut attributes = format!(
+                    " x=\"{}\" fill=\"{}\"",
+                    px(self.padding + column as f32 * cell_width),
+                    foreground
+                );
+                if style.bold {
+                    attributes.push_str(" font-weight=\"bold\"");
+                }
+                if style.italic {
+                    attributes.push_str(" font-style=\"italic\"");
+                }
+                if style.faint {
+                    attributes.push_str(" opacity=\"0.5\"");
+                }
+                let decorations: Vec<&str> = [
+                    (style.underline, "underline"),
+                    (style.strikethrough, "line-through"),
+                ]
+                .iter()
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, decoration)| *decoration)
+                .collect();
+                if !decorations.is_empty() {
+                    attributes.push_str(&format!(" text-decoration=\"{}\"", decorations.join(" ")));
+                }
+                svg.push_str(&format!("<tspan{}>{}</tspan>", attributes, escape(text)));
+            }
+            svg.push_str("</text>\n");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lays the output out on a grid of styled characters, one row per line.
+fn layout(input: &str) -> Vec<Vec<(char, TextStyle)>> {
+    let mut rows = vec![Vec::new()];
+    let mut row = 0;
+    let mut column = 0;
+    let mut style = TextStyle::default();
+    for token in parse(input) {
+        match token {
+            Token::Text(text) => {
+                for character in text.chars() {
+                    let cells = &mut rows[row];
+                    if cells.len() <= column {
+                        cells.resize(column + 1, (' ', TextStyle::default()));
+                    }
+                    cells[column] = (character, style);
+                    column += 1;
+                }
+            }
+            Token::Control(b'\n') => {
+                row += 1;
+                column = 0;
+                if rows.len() <= row {
+                    rows.push(Vec::new());
+                }
+            }
+            Token::Control(b'\r') => column = 0,
+            Token::Control(0x08) => column = column.saturating_sub(1),
+            Token::Control(b'\t') => column = (column / TAB_WIDTH + 1) * TAB_WIDTH,
+            Token::Sgr(changes) => changes.iter().for_each(|change| style.apply(change)),
+            _ => {}
+        }
+    }
+    rows
+}
+
+/// Groups a row into runs of characters sharing a style, with their starting column.
+fn runs(row: &[(char, TextStyle)]) -> Vec<(usize, TextStyle, String)> {
+    let mut runs: Vec<(usize, TextStyle, String)> = Vec::new();
+    for (column, (character, style)) in row.iter().enumerate() {
+        match runs.last_mut() {
+            Some((_, last, text)) if last == style => text.push(*character),
+            _ => runs.push((column, *style, character.to_string())),
+        }
+    }
+    runs
+}
+
+/// Tells if a run draws anything besides its background.
+fn visible(style: &TextStyle, text: &str) -> bool {
+    !style.hidden && (style.underline || style.strikethrough || !text.trim().is_empty())
+}
+
+/// Formats a length without the trailing zeros of whole numbers.
+fn px(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded.fract() == 0.0 {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{}", rounded)
+    }
+}
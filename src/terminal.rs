@@ -0,0 +1,286 @@
+/*!
+# terminal
+This module implements a headless virtual terminal. It consumes ANSI output, applies
+cursor movement, erasing and SGR changes on a grid of cells, and exposes each cell
+with its character and resolved [`TextStyle`], so tests can assert on what a user would
+see rather than on how the escape codes were encoded.
+
+# Examples
+
+```
+use rustcolor::color::Color;
+use rustcolor::printer::ColorPrinter;
+use rustcolor::terminal::Terminal;
+
+let mut terminal = Terminal::new(20, 2);
+terminal.write(&format!("ok {}", "fail".danger()));
+
+let cell = terminal.cell(3, 0).unwrap();
+assert_eq!('f', cell.character);
+assert_eq!(Some(Color::C16(1)), cell.style.foreground);
+assert_eq!("ok fail", terminal.row_text(0));
+```
+*/
+
+use crate::parser::{Parser, Token};
+use crate::style::TextStyle;
+
+const TAB_WIDTH: usize = 8;
+
+/// A character on the terminal grid, with the style it was written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub character: char,
+    pub style: TextStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            character: ' ',
+            style: TextStyle::default(),
+        }
+    }
+}
+
+/// A headless terminal grid of fixed size.
+pub struct Terminal {
+    columns: usize,
+    rows: usize,
+    cells: Vec<Cell>,
+    column: usize,
+    row: usize,
+    wrap_pending: bool,
+    saved_cursor: (usize, usize),
+    style: TextStyle,
+    parser: Parser,
+}
+
+impl Terminal {
+    /// Creates a blank terminal of the given size, with the cursor at the top left corner.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        let columns = columns.max(1);
+        let rows = rows.max(1);
+        Self {
+            columns,
+            rows,
+            cells: vec![Cell::default(); columns * rows],
+            column: 0,
+            row: 0,
+            wrap_pending: false,
+            saved_cursor: (0, 0),
+            style: TextStyle::default(),
+            parser: Parser::new(),
+        }
+    }
+
+    /// Writes terminal output.
+    pub fn write(&mut self, output: &str) {
+        self.write_bytes(output.as_bytes());
+    }
+
+    /// Writes raw terminal output, which may split sequences and characters between calls.
+    pub fn write_bytes(&mut self, output: &[u8]) {
+        for token in self.parser.advance(output) {
+            self.token(token);
+        }
+    }
+
+    /// Returns the number of columns.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the cursor position as column and row.
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.column, self.row)
+    }
+
+    /// Returns the style applied to the next characters written.
+    pub fn style(&self) -> TextStyle {
+        self.style
+    }
+
+    /// Returns the cell at the given column and row.
+    pub fn cell(&self, column: usize, row: usize) -> Option<&Cell> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+        self.cells.get(row * self.columns + column)
+    }
+
+    /// Returns the characters of a row, without trailing spaces.
+    pub fn row_text(&self, row: usize) -> String {
+        let start = row.min(self.rows) * self.columns;
+        let end = (start + self.columns).min(self.cells.len());
+        let text: String = self.cells[start..end]
+            .iter()
+            .map(|cell| cell.character)
+            .collect();
+        text.trim_end().to_owned()
+    }
+
+    /// Returns the characters of the whole grid, one line per row, without trailing blank lines.
+    pub fn text(&self) -> String {
+        let rows: Vec<String> = (0..self.rows).map(|row| self.row_text(row)).collect();
+        rows.join("\n").trim_end_matches('\n').to_owned()
+    }
+
+    fn token(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => text.chars().for_each(|character| self.print(character)),
+            Token::Control(byte) => self.control(byte),
+            Token::Sgr(changes) => changes.iter().for_each(|change| self.style.apply(change)),
+            Token::Csi {
+                params,
+                intermediates,
+                action,
+            } if intermediates.is_empty() => self.csi(&params, action),
+            Token::Esc {
+                intermediates,
+                action,
+            } if intermediates.is_empty() => match action {
+                '7' => self.saved_cursor = (self.column, self.row),
+                '8' => self.move_to(self.saved_cursor.0, self.saved_cursor.1),
+                'D' => self.line_feed(),
+                'E' => {
+                    self.column = 0;
+                    self.line_feed();
+                }
+                'M' => self.reverse_line_feed(),
+                'c' => *self = Terminal::new(self.columns, self.rows),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn print(&mut self, character: char) {
+        if self.wrap_pending {
+            self.column = 0;
+            self.line_feed();
+        }
+        let index = self.row * self.columns + self.column;
+        self.cells[index] = Cell {
+            character,
+            style: self.style,
+        };
+        if self.column + 1 < self.columns {
+            self.column += 1;
+        } else {
+            self.wrap_pending = true;
+        }
+    }
+
+    fn control(&mut self, byte: u8) {
+        match byte {
+            b'\n' | 0x0b | 0x0c => self.line_feed(),
+            b'\r' => self.move_to(0, self.row),
+            0x08 => self.move_to(self.column.saturating_sub(1), self.row),
+            b'\t' => self.move_to((self.column / TAB_WIDTH + 1) * TAB_WIDTH, self.row),
+            _ => {}
+        }
+    }
+
+    fn csi(&mut self, params: &[Vec<u16>], action: char) {
+        let param = |index: usize, default: usize| {
+            params
+                .get(index)
+                .map(|param| param[0] as usize)
+                .filter(|value| *value != 0)
+                .unwrap_or(default)
+        };
+        let (column, row) = (self.column, self.row);
+        match action {
+            'A' => self.move_to(column, row.saturating_sub(param(0, 1))),
+            'B' | 'e' => self.move_to(column, row + param(0, 1)),
+            'C' | 'a' => self.move_to(column + param(0, 1), row),
+            'D' => self.move_to(column.saturating_sub(param(0, 1)), row),
+            'E' => self.move_to(0, row + param(0, 1)),
+            'F' => self.move_to(0, row.saturating_sub(param(0, 1))),
+            'G' | '`' => self.move_to(param(0, 1) - 1, row),
+            'd' => self.move_to(column, param(0, 1) - 1),
+            'H' | 'f' => self.move_to(param(1, 1) - 1, param(0, 1) - 1),
+            'J' => match params.first().map_or(0, |param| param[0]) {
+                0 => self.erase(row * self.columns + column, self.cells.len()),
+                1 => self.erase(0, row * self.columns + column + 1),
+                _ => self.erase(0, self.cells.len()),
+            },
+            'K' => {
+                let start = row * self.columns;
+                match params.first().map_or(0, |param| param[0]) {
+                    0 => self.erase(start + column, start + self.columns),
+                    1 => self.erase(start, start + column + 1),
+                    _ => self.erase(start, start + self.columns),
+                }
+            }
+            'X' => {
+                let start = row * self.columns + column;
+                let end = start + param(0, 1).min(self.columns - column);
+                self.erase(start, end);
+            }
+            'S' => (0..param(0, 1)).for_each(|_| self.scroll_up()),
+            's' => self.saved_cursor = (column, row),
+            'u' => self.move_to(self.saved_cursor.0, self.saved_cursor.1),
+            _ => {}
+        }
+    }
+
+    fn move_to(&mut self, column: usize, row: usize) {
+        self.column = column.min(self.columns - 1);
+        self.row = row.min(self.rows - 1);
+        self.wrap_pending = false;
+    }
+
+    fn line_feed(&mut self) {
+        if self.row + 1 < self.rows {
+            self.row += 1;
+        } else {
+            self.scroll_up();
+        }
+        self.wrap_pending = false;
+    }
+
+    fn reverse_line_feed(&mut self) {
+        if self.row > 0 {
+            self.row -= 1;
+        } else {
+            self.cells.truncate(self.columns * (self.rows - 1));
+            let blank = vec![self.blank(); self.columns];
+            self.cells.splice(0..0, blank);
+        }
+        self.wrap_pending = false;
+    }
+
+    fn scroll_up(&mut self) {
+        self.cells.drain(..self.columns);
+        let blank = self.blank();
+        self.cells.resize(self.columns * self.rows, blank);
+    }
+
+    fn erase(&mut self, start: usize, end: usize) {
+        let blank = self.blank();
+        let end = end.min(self.cells.len());
+        if start < end {
+            self.cells[start..end]
+                .iter_mut()
+                .for_each(|cell| *cell = blank);
+        }
+    }
+
+    /// Erased cells keep the current background, as terminals do.
+    fn blank(&self) -> Cell {
+        Cell {
+            character: ' ',
+            style: TextStyle {
+                background: self.style.background,
+                ..TextStyle::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,478 @@
+/*!
+# theme
+This module maps semantic roles, such as info, warn or link, to styles. The printer
+presets and the macros render through the active theme: the theme of the innermost
+[`scope`] of the current thread, else the process wide theme set by [`set_default`],
+else the dark theme, whose styles are the historical preset colors. Applications can
+[`register`](Role::register) their own roles besides the built-in ones.
+
+The same colors are not readable on every terminal: yellow text fades on a white
+background, while it stands out on a black one. A theme therefore comes with a dark
+and a light variant, and an [`AdaptiveTheme`] picks one from the terminal background,
+queried with OSC 11, or from the `COLORFGBG` variable some terminals set, falling back
+to a configurable appearance.
+
+The presets do not adapt on their own, since querying the terminal writes to it and
+waits for its reply: an application makes them follow the background by setting the
+selected variant as the default theme once at startup, before printing.
+
+```no_run
+use rustcolor::theme::{self, AdaptiveTheme};
+
+theme::set_default(AdaptiveTheme::default().select().clone());
+```
+
+# Examples
+
+```
+use rustcolor::color::RGB;
+use rustcolor::printer::ColorPrinter;
+use rustcolor::style::TextStyle;
+use rustcolor::theme::{self, AdaptiveTheme, Appearance, Role, Theme};
+
+let theme = Theme::dark();
+assert_eq!("\u{001b}[33;49mcareful\u{001b}[0m", theme.paint(Role::Warn, "careful"));
+
+assert_eq!(Appearance::Light, Appearance::from_background(RGB(250, 250, 250)));
+assert_eq!(Some(Appearance::Dark), Appearance::from_colorfgbg("15;0"));
+
+let themes = AdaptiveTheme::new(Theme::dark(), Theme::light()).fallback(Appearance::Light);
+assert_eq!(&Theme::light(), themes.select_for(None));
+assert_eq!(&Theme::dark(), themes.select_for(Some(Appearance::Dark)));
+
+let deploy = Role::register("deploy");
+{
+    let _guard = theme::scope(Theme::light().set(deploy, TextStyle::from_sgr("35").unwrap()));
+    assert_eq!("\u{001b}[35mv1.2\u{001b}[0m", "v1.2".role(deploy));
+}
+assert_eq!("v1.2", "v1.2".role(deploy));
+```
+*/
+
+use crate::color::{Color, RGB};
+use crate::contrast::contrast_ratio;
+use crate::style::TextStyle;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::env;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+static DEFAULT: RwLock<Option<Arc<Theme>>> = RwLock::new(None);
+static CUSTOM_ROLES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static BUILT_IN: Arc<Theme> = Arc::new(Theme::default());
+    static SCOPES: RefCell<Vec<(usize, Arc<Theme>)>> = const { RefCell::new(Vec::new()) };
+    static NEXT_SCOPE: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The role of a text, styled by a theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Neutral information.
+    Info,
+    /// An operation that went well.
+    Success,
+    /// The main point of an output.
+    Primary,
+    /// Something that may need attention.
+    Warn,
+    /// Something that went wrong or may break.
+    Danger,
+    /// A failure, standing out from everything else.
+    Error,
+    /// Secondary details, such as timestamps.
+    Muted,
+    /// Text stressed within a sentence.
+    Emphasis,
+    /// Commands, paths and identifiers.
+    Code,
+    /// URLs and references.
+    Link,
+    /// Blinking text.
+    Blink,
+    /// Underlined text.
+    Underline,
+    /// A role defined by the application, see [`Role::register`].
+    Custom(&'static str),
+}
+
+impl Role {
+    /// All the roles.
+    pub const ALL: [Role; 12] = [
+        Role::Info,
+        Role::Success,
+        Role::Primary,
+        Role::Warn,
+        Role::Danger,
+        Role::Error,
+        Role::Muted,
+        Role::Emphasis,
+        Role::Code,
+        Role::Link,
+        Role::Blink,
+        Role::Underline,
+    ];
+
+    /// Returns the lowercase name of the role.
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Info => "info",
+            Role::Success => "success",
+            Role::Primary => "primary",
+            Role::Warn => "warn",
+            Role::Danger => "danger",
+            Role::Error => "error",
+            Role::Muted => "muted",
+            Role::Emphasis => "emphasis",
+            Role::Code => "code",
+            Role::Link => "link",
+            Role::Blink => "blink",
+            Role::Underline => "underline",
+            Role::Custom(name) => name,
+        }
+    }
+
+    /// Returns the built-in or registered role of a name.
+    pub fn from_name(name: &str) -> Option<Role> {
+        Role::ALL
+            .iter()
+            .copied()
+            .find(|role| role.name() == name)
+            .or_else(|| {
+                let roles = CUSTOM_ROLES.lock().unwrap_or_else(PoisonError::into_inner);
+                roles
+                    .iter()
+                    .find(|role| **role == name)
+                    .map(|role| Role::Custom(role))
+            })
+    }
+
+    /// Registers a custom role name, so [`Role::from_name`] finds it, and returns its role.
+    /// Registering a name again, or a built-in name, returns the existing role.
+    pub fn register(name: &str) -> Role {
+        if let Some(role) = Role::from_name(name) {
+            return role;
+        }
+        let mut roles = CUSTOM_ROLES.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(role) = roles.iter().find(|role| **role == name) {
+            return Role::Custom(role);
+        }
+        let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        roles.push(name);
+        Role::Custom(name)
+    }
+
+    /// Returns the registered custom roles, in registration order.
+    pub fn registered() -> Vec<Role> {
+        let roles = CUSTOM_ROLES.lock().unwrap_or_else(PoisonError::into_inner);
+        roles.iter().map(|role| Role::Custom(role)).collect()
+    }
+}
+
+/// Whether a terminal shows light text on a dark background, or the opposite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Appearance {
+    Dark,
+    Light,
+}
+
+impl Appearance {
+    /// Returns the appearance of a background color, light when black text contrasts
+    /// more with it than white text.
+    pub fn from_background(background: RGB) -> Appearance {
+        if contrast_ratio(background, RGB(0, 0, 0)) > contrast_ratio(background, RGB(255, 255, 255))
+        {
+            Appearance::Light
+        } else {
+            Appearance::Dark
+        }
+    }
+
+    /// Returns the appearance described by a `COLORFGBG` value, such as `15;0` or
+    /// `0;default;15`, whose last field is the background color index.
+    pub fn from_colorfgbg(value: &str) -> Option<Appearance> {
+        match value.rsplit(';').next()?.trim().parse::<u8>().ok()? {
+            7 | 9..=15 => Some(Appearance::Light),
+            0..=6 | 8 => Some(Appearance::Dark),
+            _ => None,
+        }
+    }
+
+    /// Detects the appearance of the terminal, from its background color on unix,
+    /// then from the `COLORFGBG` variable.
+    pub fn detect() -> Option<Appearance> {
+        query_background()
+            .map(Appearance::from_background)
+            .or_else(|| {
+                env::var("COLORFGBG")
+                    .ok()
+                    .and_then(|value| Appearance::from_colorfgbg(&value))
+            })
+    }
+}
+
+#[cfg(unix)]
+fn query_background() -> Option<RGB> {
+    crate::query::Query::tty().ok()?.background().ok()
+}
+
+#[cfg(not(unix))]
+fn query_background() -> Option<RGB> {
+    None
+}
+
+/// The styles of the roles.
+///
+/// A theme deserialized with the `serde` feature holds only the roles it lists, so a
+/// configuration file overriding a few roles is merged over a preset:
+/// `Theme::dark().merge(loaded)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    styles: HashMap<Role, TextStyle>,
+}
+
+impl Theme {
+    /// Creates a theme without styles, every role rendering plain text.
+    pub fn new() -> Self {
+        Self {
+            styles: HashMap::new(),
+        }
+    }
+
+    /// The styles for dark backgrounds, the historical printer colors.
+    pub fn dark() -> Self {
+        Theme::new()
+            .set(Role::Info, on_default(Color::C16(2)))
+            .set(
+                Role::Success,
+                TextStyle {
+                    bold: true,
+                    ..foreground(Color::C16(2))
+                },
+            )
+            .set(Role::Primary, on_default(Color::C16(4)))
+            .set(Role::Warn, on_default(Color::C16(3)))
+            .set(Role::Danger, on_default(Color::C16(1)))
+            .set(
+                Role::Error,
+                TextStyle {
+                    background: Some(Color::C16(1)),
+                    ..foreground(Color::C16(7))
+                },
+            )
+            .set(Role::Muted, foreground(Color::C16(8)))
+            .set(
+                Role::Emphasis,
+                TextStyle {
+                    bold: true,
+                    ..TextStyle::default()
+                },
+            )
+            .set(Role::Code, foreground(Color::C16(6)))
+            .set(
+                Role::Link,
+                TextStyle {
+                    underline: true,
+                    ..foreground(Color::C16(4))
+                },
+            )
+            .set(
+                Role::Blink,
+                TextStyle {
+                    blink: true,
+                    ..foreground(Color::C16(1))
+                },
+            )
+            .set(
+                Role::Underline,
+                TextStyle {
+                    underline: true,
+                    ..foreground(Color::C16(3))
+                },
+            )
+    }
+
+    /// The styles for light backgrounds, with darker greens, yellows, cyans and grays.
+    pub fn light() -> Self {
+        Theme::dark()
+            .set(Role::Info, on_default(Color::C256(28)))
+            .set(
+                Role::Success,
+                TextStyle {
+                    bold: true,
+                    ..foreground(Color::C256(28))
+                },
+            )
+            .set(Role::Warn, on_default(Color::C256(130)))
+            .set(Role::Muted, foreground(Color::C256(243)))
+            .set(Role::Code, foreground(Color::C256(23)))
+            .set(
+                Role::Underline,
+                TextStyle {
+                    underline: true,
+                    ..foreground(Color::C256(130))
+                },
+            )
+    }
+
+    /// Sets the style of a role.
+    pub fn set(mut self, role: Role, style: TextStyle) -> Theme {
+        self.styles.insert(role, style);
+        self
+    }
+
+    /// Returns the theme with the styles of another theme set over its own, as a
+    /// partial theme read from a configuration file over a preset.
+    pub fn merge(mut self, other: Theme) -> Theme {
+        self.styles.extend(other.styles);
+        self
+    }
+
+    /// Returns the style of a role, the empty style if the theme does not define it.
+    pub fn style(&self, role: Role) -> TextStyle {
+        self.styles.get(&role).copied().unwrap_or_default()
+    }
+
+    /// Renders a text with the style of a role.
+    pub fn paint(&self, role: Role, text: &str) -> String {
+        self.style(role).render(text)
+    }
+
+    /// Returns the roles the theme defines, with their names and styles, in the order
+    /// of [`Role::ALL`] followed by the custom roles sorted by name.
+    pub fn styles(&self) -> Vec<(&'static str, TextStyle)> {
+        let mut custom: Vec<(&'static str, TextStyle)> = self
+            .styles
+            .iter()
+            .filter_map(|(role, style)| match role {
+                Role::Custom(name) => Some((*name, *style)),
+                _ => None,
+            })
+            .collect();
+        custom.sort_by_key(|(name, _)| *name);
+        Role::ALL
+            .iter()
+            .filter_map(|role| self.styles.get(role).map(|style| (role.name(), *style)))
+            .chain(custom)
+            .collect()
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+/// A dark and a light theme, one of which is selected by the terminal appearance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptiveTheme {
+    dark: Theme,
+    light: Theme,
+    fallback: Appearance,
+}
+
+impl AdaptiveTheme {
+    /// Creates an adaptive theme, falling back to the dark variant.
+    pub fn new(dark: Theme, light: Theme) -> Self {
+        Self {
+            dark,
+            light,
+            fallback: Appearance::Dark,
+        }
+    }
+
+    /// Sets the appearance assumed when it can not be detected.
+    pub fn fallback(mut self, fallback: Appearance) -> AdaptiveTheme {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Returns the variant matching an appearance, or the fallback variant.
+    pub fn select_for(&self, appearance: Option<Appearance>) -> &Theme {
+        match appearance.unwrap_or(self.fallback) {
+            Appearance::Dark => &self.dark,
+            Appearance::Light => &self.light,
+        }
+    }
+
+    /// Returns the variant matching the detected terminal appearance.
+    pub fn select(&self) -> &Theme {
+        self.select_for(Appearance::detect())
+    }
+}
+
+impl Default for AdaptiveTheme {
+    fn default() -> Self {
+        AdaptiveTheme::new(Theme::dark(), Theme::light())
+    }
+}
+
+/// Sets the theme of the whole process, used by threads outside of any [`scope`].
+pub fn set_default(theme: Theme) {
+    *DEFAULT.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(theme));
+}
+
+/// Restores the built-in dark theme as the theme of the whole process.
+pub fn reset_default() {
+    *DEFAULT.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Makes a theme active on the current thread until the returned guard is dropped.
+/// Scopes nest, the innermost one winning.
+pub fn scope(theme: Theme) -> ScopedTheme {
+    let id = NEXT_SCOPE.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    });
+    SCOPES.with(|scopes| scopes.borrow_mut().push((id, Arc::new(theme))));
+    ScopedTheme {
+        id,
+        thread: PhantomData,
+    }
+}
+
+/// Calls a function with the active theme, which the printer presets render through.
+pub fn active<T>(f: impl FnOnce(&Theme) -> T) -> T {
+    let theme = SCOPES
+        .with(|scopes| scopes.borrow().last().map(|(_, theme)| Arc::clone(theme)))
+        .or_else(|| {
+            DEFAULT
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        })
+        .unwrap_or_else(|| BUILT_IN.with(Arc::clone));
+    f(&theme)
+}
+
+/// The guard of a theme made active by [`scope`], which deactivates it when dropped.
+pub struct ScopedTheme {
+    id: usize,
+    // the guard must be dropped on the thread of its scope
+    thread: PhantomData<*const ()>,
+}
+
+impl Drop for ScopedTheme {
+    fn drop(&mut self) {
+        let _ = SCOPES.try_with(|scopes| scopes.borrow_mut().retain(|(id, _)| *id != self.id));
+    }
+}
+
+/// A foreground color on the explicit default background, as the presets print it.
+fn on_default(color: Color) -> TextStyle {
+    TextStyle {
+        background: Some(Color::Default),
+        ..foreground(color)
+    }
+}
+
+fn foreground(color: Color) -> TextStyle {
+    TextStyle {
+        foreground: Some(color),
+        ..TextStyle::default()
+    }
+}
@@ -0,0 +1,173 @@
+/*!
+# vision
+This module simulates how colors appear to people with a color vision deficiency, and
+checks that the styles of a theme stay distinguishable for them. Dichromacies use the
+[Machado et al. (2009)](https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html)
+matrices in linear sRGB, and anomalous trichromacies are interpolated between normal
+vision and the matching dichromacy by their severity.
+
+# Examples
+
+```
+use rustcolor::color::RGB;
+use rustcolor::palette::Palette;
+use rustcolor::style::TextStyle;
+use rustcolor::vision::{self, Deficiency};
+
+let red = RGB(255, 0, 0);
+assert_eq!(red, red.simulate(Deficiency::Protanomaly(0.0)));
+assert_ne!(red, red.simulate(Deficiency::Protanopia));
+
+// the green info and yellow warn presets look alike without red or green cones
+let styles = [
+    ("info", TextStyle::from_sgr("32").unwrap()),
+    ("warn", TextStyle::from_sgr("33").unwrap()),
+];
+for conflict in vision::conflicts(&styles, &Palette::default(), vision::THRESHOLD) {
+    println!("{} and {} look alike with {:?}", conflict.first, conflict.second, conflict.deficiency);
+}
+```
+*/
+
+use crate::color::RGB;
+use crate::palette::Palette;
+use crate::space::{Lab, LinearRgb};
+use crate::style::TextStyle;
+
+/// The CIEDE2000 distance under which two colors are considered indistinguishable.
+pub const THRESHOLD: f64 = 10.0;
+
+/// The deficiencies checked by [`conflicts`], the most severe of each kind.
+pub const DICHROMACIES: [Deficiency; 3] = [
+    Deficiency::Protanopia,
+    Deficiency::Deuteranopia,
+    Deficiency::Tritanopia,
+];
+
+const PROTANOPIA: [[f64; 3]; 3] = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+
+const DEUTERANOPIA: [[f64; 3]; 3] = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+
+const TRITANOPIA: [[f64; 3]; 3] = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+/// A color vision deficiency. Anomalous variants carry a severity in 0.0-1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Deficiency {
+    /// No red cones.
+    Protanopia,
+    /// No green cones.
+    Deuteranopia,
+    /// No blue cones.
+    Tritanopia,
+    /// Red cones shifted toward green.
+    Protanomaly(f64),
+    /// Green cones shifted toward red.
+    Deuteranomaly(f64),
+    /// Blue cones shifted toward green.
+    Tritanomaly(f64),
+}
+
+impl Deficiency {
+    /// Returns the dichromacy matrix and the severity of the deficiency.
+    fn matrix(self) -> (&'static [[f64; 3]; 3], f64) {
+        match self {
+            Deficiency::Protanopia => (&PROTANOPIA, 1.0),
+            Deficiency::Deuteranopia => (&DEUTERANOPIA, 1.0),
+            Deficiency::Tritanopia => (&TRITANOPIA, 1.0),
+            Deficiency::Protanomaly(severity) => (&PROTANOPIA, severity.clamp(0.0, 1.0)),
+            Deficiency::Deuteranomaly(severity) => (&DEUTERANOPIA, severity.clamp(0.0, 1.0)),
+            Deficiency::Tritanomaly(severity) => (&TRITANOPIA, severity.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+impl RGB {
+    /// Returns the color as seen with a color vision deficiency.
+    pub fn simulate(self, deficiency: Deficiency) -> RGB {
+        let (matrix, severity) = deficiency.matrix();
+        let LinearRgb { r, g, b } = LinearRgb::from(self);
+        let channel = |row: &[f64; 3], original: f64| {
+            let simulated = row[0] * r + row[1] * g + row[2] * b;
+            original + (simulated - original) * severity
+        };
+        RGB::from(LinearRgb {
+            r: channel(&matrix[0], r),
+            g: channel(&matrix[1], g),
+            b: channel(&matrix[2], b),
+        })
+    }
+}
+
+/// Two styles which can be told apart with normal vision but not with a deficiency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub first: String,
+    pub second: String,
+    pub deficiency: Deficiency,
+    /// The CIEDE2000 distance of the simulated colors.
+    pub distance: f64,
+}
+
+/// Checks every pair of named styles against the [`DICHROMACIES`], colors being resolved
+/// through the palette. Styles differing by an attribute such as bold or underline are
+/// never reported, as they stay distinguishable whatever their colors.
+pub fn conflicts(styles: &[(&str, TextStyle)], palette: &Palette, threshold: f64) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    for (index, (first, first_style)) in styles.iter().enumerate() {
+        for (second, second_style) in styles.iter().skip(index + 1) {
+            if !same_attributes(first_style, second_style) {
+                continue;
+            }
+            let first_colors = palette.resolve_style(first_style);
+            let second_colors = palette.resolve_style(second_style);
+            if distance(first_colors, second_colors, None) < threshold {
+                continue;
+            }
+            for deficiency in DICHROMACIES.iter() {
+                let distance = distance(first_colors, second_colors, Some(*deficiency));
+                if distance < threshold {
+                    conflicts.push(Conflict {
+                        first: first.to_string(),
+                        second: second.to_string(),
+                        deficiency: *deficiency,
+                        distance,
+                    });
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+/// Returns the largest distance between the foregrounds and between the backgrounds.
+fn distance(first: (RGB, RGB), second: (RGB, RGB), deficiency: Option<Deficiency>) -> f64 {
+    let lab = |color: RGB| match deficiency {
+        Some(deficiency) => Lab::from(color.simulate(deficiency)),
+        None => Lab::from(color),
+    };
+    let foreground = lab(first.0).delta_e_2000(&lab(second.0));
+    let background = lab(first.1).delta_e_2000(&lab(second.1));
+    foreground.max(background)
+}
+
+fn same_attributes(first: &TextStyle, second: &TextStyle) -> bool {
+    let attributes = |style: &TextStyle| TextStyle {
+        foreground: None,
+        background: None,
+        reverse: false,
+        ..*style
+    };
+    attributes(first) == attributes(second)
+}
@@ -0,0 +1,22 @@
+//! Forcing hyperlinks on and off changes process wide state, so it is tested in its own
+//! test binary rather than next to the unit tests printing links.
+
+use rustcolor::hyperlink::{self, Hyperlink};
+use rustcolor::printer::ColorPrinter;
+
+#[test]
+fn test_hyperlinks_forced() {
+    hyperlink::set_enabled(Some(false));
+    assert!(!hyperlink::enabled());
+    assert_eq!("plain", "plain".hyperlink("https://example.com"));
+
+    hyperlink::set_enabled(Some(true));
+    assert!(hyperlink::enabled());
+    assert_eq!(
+        Hyperlink::new("https://example.com").wrap("linked"),
+        "linked".hyperlink("https://example.com")
+    );
+
+    hyperlink::set_enabled(None);
+    assert_eq!(hyperlink::supported(), hyperlink::enabled());
+}
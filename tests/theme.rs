@@ -0,0 +1,30 @@
+//! Setting the default theme changes process wide state, so it is tested in its own
+//! test binary rather than next to the unit tests printing presets.
+
+use rustcolor::printer::ColorPrinter;
+use rustcolor::style::TextStyle;
+use rustcolor::theme::{self, Role, Theme};
+
+#[test]
+fn test_theme_default() {
+    let audit = Role::register("audit");
+    let style = TextStyle::from_sgr("1;35").unwrap();
+    let warn = "w".warn();
+
+    theme::set_default(Theme::light().set(audit, style));
+    let painted = "entry".role(audit);
+    let from_thread = std::thread::spawn(move || "entry".role(audit))
+        .join()
+        .unwrap();
+    assert_eq!("\u{001b}[35;1mentry\u{001b}[0m", painted);
+    assert_eq!(painted, from_thread);
+    assert_eq!(Theme::light().paint(Role::Warn, "w"), "w".warn());
+    {
+        let _guard = theme::scope(Theme::dark());
+        assert_eq!(warn, "w".warn());
+    }
+
+    theme::reset_default();
+    assert_eq!("entry", "entry".role(audit));
+    assert_eq!(warn, "w".warn());
+}